@@ -2,12 +2,12 @@ use solana_program::entrypoint::ProgramResult;
 use solana_program::msg;
 use solana_program::program_error::ProgramError;
 use std::fmt::{Debug, Display};
-use solana_program::pubkey::Pubkey;
 use thiserror::Error;
+use crate::state::multisig::Owner;
 
 #[derive(Debug, Error)]
 pub enum MultisigError {
-    #[error("Threshold must be less than or equal to the number of owners and greater than zero.")]
+    #[error("Threshold must be less than or equal to the total weight of the voting owners and greater than zero.")]
     InvalidThreshold,
     #[error("Owners must be unique.")]
     UniqueOwners,
@@ -21,7 +21,7 @@ pub enum MultisigError {
     InvalidOwnerSetSequenceNumber,
     #[error("The number of owners must be greater than zero.")]
     NotEnoughOwners,
-    #[error("The number of owners must not be increased above its original value.")]
+    #[error("The number of owners can only be increased above its original value if a payer for the additional space is provided.")]
     TooManyOwners,
     #[error("The executor must be a signer and an owner of this multisig.")]
     InvalidExecutor,
@@ -47,6 +47,66 @@ pub enum MultisigError {
     MalformedMultisigAccount,
     #[error("The given account is owned by a different program than expected.")]
     AccountOwnedByWrongProgram,
+    #[error("The time lock of the transaction has not elapsed since the approval threshold was reached.")]
+    TimeLockNotElapsed,
+    #[error("The transaction has expired.")]
+    TransactionExpired,
+    #[error("The transaction can still be executed and must be cancelled by an owner instead.")]
+    TransactionNotStale,
+    #[error("The transaction has been rejected by the owners.")]
+    TransactionRejected,
+    #[error("Cancel threshold must be less than or equal to the number of voting owners and greater than zero.")]
+    InvalidCancelThreshold,
+    #[error("Owner permissions must be non-empty and every permission must be held by at least one owner.")]
+    InvalidPermissions,
+    #[error("The owner does not have the permission required for this instruction.")]
+    MissingPermission,
+    #[error("Owners with vote permission must have a weight greater than zero.")]
+    InvalidWeight,
+    #[error("The layout version of the given account is not supported by this program.")]
+    UnsupportedAccountVersion,
+    #[error("The multisig account must be writable.")]
+    ImmutableMultisigAccount,
+    #[error("The given spending limit account is missing or not in the expected format.")]
+    MalformedSpendingLimitAccount,
+    #[error("Spending limits must have a non-zero amount and period and unique members.")]
+    InvalidSpendingLimit,
+    #[error("The member must be a signer, an owner of this multisig and a member of the spending limit.")]
+    InvalidSpendingLimitMember,
+    #[error("The spending limit does not belong to the given multisig or mint.")]
+    InvalidSpendingLimitAccount,
+    #[error("The amount exceeds the remaining spending limit of the current period.")]
+    SpendingLimitExceeded,
+    #[error("The given transaction buffer account is missing or not in the expected format.")]
+    MalformedTransactionBufferAccount,
+    #[error("Only the creator of a transaction buffer can extend or finalize it.")]
+    InvalidTransactionBufferCreator,
+    #[error("The transaction buffer must not be empty or exceed its final size.")]
+    InvalidTransactionBufferSize,
+    #[error("The transaction buffer is incomplete or does not match its final hash.")]
+    TransactionBufferHashMismatch,
+    #[error("A lookup account refers to a missing address lookup table, address or position.")]
+    InvalidAddressLookupTable,
+    #[error("The multisig cannot be closed while it has pending transactions.")]
+    PendingTransactions,
+    #[error("No ed25519 signature over the transaction content hash by an owner allowed to vote was found.")]
+    MissingSignatureApproval,
+    #[error("The maximum number of instructions to execute must be greater than zero.")]
+    InvalidMaxInstructions,
+    #[error("The config actions must not be empty and may only remove owners of the multisig.")]
+    InvalidConfigActions,
+    #[error("The given account does not match the rent payer of the transaction.")]
+    RentPayerMismatch,
+    #[error("The memo of a transaction must not exceed 64 bytes.")]
+    MemoTooLong,
+    #[error("The content hash of the transaction does not match the expected hash.")]
+    ContentHashMismatch,
+    #[error("The multisig still has spending limits, which have to be removed before it can be closed.")]
+    ActiveSpendingLimits,
+    #[error("A transaction must not have more than 65535 instructions.")]
+    TooManyInstructions,
+    #[error("The owner must be a signer.")]
+    OwnerNotSigner,
 }
 
 impl From<MultisigError> for ProgramError {
@@ -77,10 +137,10 @@ pub fn assert_that(condition: bool, error: impl Into<ProgramError> + Debug + Dis
     }
 }
 
-pub fn assert_unique_owners(owners: &[Pubkey]) -> ProgramResult {
+pub fn assert_unique_owners(owners: &[Owner]) -> ProgramResult {
     for (i, owner) in owners.iter().enumerate() {
         assert_that(
-            !owners.iter().skip(i + 1).any(|item| item == owner),
+            !owners.iter().skip(i + 1).any(|item| item.pubkey == owner.pubkey),
             MultisigError::UniqueOwners,
         )?
     }
@@ -0,0 +1,139 @@
+use crate::state::multisig::Owner;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::log::sol_log_data;
+use solana_program::pubkey::Pubkey;
+
+/// Events logged via `sol_log_data` (a single Borsh-serialized `MultisigEvent` per "Program data:" log entry) on every
+/// state transition, so that indexers can follow multisigs and their transactions without parsing `msg!` logs. New
+/// variants must be appended at the end to keep the encoding of the existing ones stable.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub enum MultisigEvent {
+    MultisigCreated {
+        multisig: Pubkey,
+        owners: Vec<Owner>,
+        threshold: u16,
+        cancel_threshold: u8,
+        time_lock_seconds: u32,
+    },
+    OwnersChanged {
+        multisig: Pubkey,
+        owners: Vec<Owner>,
+        owner_set_seqno: u32,
+        // the thresholds are lowered along with the owners if they could no longer be reached
+        threshold: u16,
+        cancel_threshold: u8,
+    },
+    ThresholdChanged {
+        multisig: Pubkey,
+        threshold: u16,
+    },
+    CancelThresholdChanged {
+        multisig: Pubkey,
+        cancel_threshold: u8,
+    },
+    TimeLockChanged {
+        multisig: Pubkey,
+        time_lock_seconds: u32,
+    },
+    TransactionProposed {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        proposer: Pubkey,
+        transaction_index: u64,
+        vault_index: u8,
+        approval_weight: u32,
+        threshold_reached_at: i64,
+    },
+    TransactionApproved {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        approver: Pubkey,
+        approval_weight: u32,
+        threshold_reached_at: i64,
+    },
+    ApprovalRevoked {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        owner: Pubkey,
+        approval_weight: u32,
+        threshold_reached_at: i64,
+    },
+    TransactionRejected {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        rejector: Pubkey,
+        rejection_weight: u32,
+        rejected: bool,
+    },
+    TransactionExecuted {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        executor: Pubkey,
+    },
+    CancelVoted {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        canceller: Pubkey,
+        cancellation_count: u8,
+    },
+    TransactionCancelled {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        canceller: Pubkey,
+    },
+    StaleTransactionClosed {
+        multisig: Pubkey,
+        transaction: Pubkey,
+    },
+    InstructionsExecuted {
+        multisig: Pubkey,
+        transaction: Pubkey,
+        executor: Pubkey,
+        executed_up_to: u16,
+    },
+    MultisigClosed {
+        multisig: Pubkey,
+        recipient: Pubkey,
+    },
+    SpendingLimitAdded {
+        multisig: Pubkey,
+        spending_limit: Pubkey,
+        vault_index: u8,
+        mint: Pubkey,
+        amount: u64,
+        period_seconds: i64,
+        members: Vec<Pubkey>,
+    },
+    SpendingLimitRemoved {
+        multisig: Pubkey,
+        spending_limit: Pubkey,
+    },
+    SpendingLimitUsed {
+        multisig: Pubkey,
+        spending_limit: Pubkey,
+        member: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+        remaining_amount: u64,
+    },
+    TransactionBufferCreated {
+        multisig: Pubkey,
+        transaction_buffer: Pubkey,
+        creator: Pubkey,
+        buffer_index: u8,
+        final_size: u32,
+    },
+    TransactionBufferClosed {
+        multisig: Pubkey,
+        transaction_buffer: Pubkey,
+        creator: Pubkey,
+    },
+}
+
+impl MultisigEvent {
+    pub fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&borsh::to_vec(self)?]);
+        Ok(())
+    }
+}
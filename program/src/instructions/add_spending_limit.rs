@@ -0,0 +1,105 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{create_pda_account, validate_signer};
+use crate::state::multisig::Multisig;
+use crate::state::spending_limit::SpendingLimit;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::account_info::next_account_info;
+use solana_program::clock::Clock;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+use solana_sdk_ids::system_program;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AddSpendingLimitInstruction {
+    pub vault_index: u8,
+    // `Pubkey::default()` for lamports
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub period_seconds: i64,
+    pub members: Vec<Pubkey>,
+}
+
+pub fn add_spending_limit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: AddSpendingLimitInstruction,
+) -> ProgramResult {
+    msg!("invoke add_spending_limit - {:?}", instruction);
+
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let multisig_signer = next_account_info(accounts_iter)?;
+    let spending_limit_account = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(system_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
+    assert_that(multisig_account.is_writable, MultisigError::ImmutableMultisigAccount)?;
+    let mut multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    validate_signer(multisig_signer, multisig_account, &multisig, program_id)?;
+    validate(&multisig, &instruction)?;
+
+    let (spending_limit_address, bump) =
+        SpendingLimit::find_address(multisig_account.key, instruction.vault_index, &instruction.mint, program_id);
+    assert_that(*spending_limit_account.key == spending_limit_address, ProgramError::InvalidSeeds)?;
+
+    let spending_limit = SpendingLimit {
+        discriminator: SpendingLimit::DISCRIMINATOR,
+        version: SpendingLimit::VERSION,
+        multisig: *multisig_account.key,
+        vault_index: instruction.vault_index,
+        mint: instruction.mint,
+        amount: instruction.amount,
+        period_seconds: instruction.period_seconds,
+        members: instruction.members,
+        remaining_amount: instruction.amount,
+        period_started_at: Clock::get()?.unix_timestamp,
+    };
+    create_pda_account(
+        payer,
+        spending_limit_account,
+        system_program,
+        spending_limit.len(),
+        program_id,
+        &[
+            multisig_account.key.as_ref(),
+            SpendingLimit::SEED_PREFIX,
+            &[instruction.vault_index],
+            instruction.mint.as_ref(),
+            &[bump],
+        ],
+    )?;
+    spending_limit.serialize(&mut &mut spending_limit_account.data.borrow_mut()[..])?;
+
+    multisig.spending_limits = multisig.spending_limits.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    multisig.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+
+    MultisigEvent::SpendingLimitAdded {
+        multisig: *multisig_account.key,
+        spending_limit: *spending_limit_account.key,
+        vault_index: spending_limit.vault_index,
+        mint: spending_limit.mint,
+        amount: spending_limit.amount,
+        period_seconds: spending_limit.period_seconds,
+        members: spending_limit.members,
+    }
+    .emit()
+}
+
+fn validate(multisig: &Multisig, instruction: &AddSpendingLimitInstruction) -> ProgramResult {
+    assert_that(
+        instruction.amount > 0 && instruction.period_seconds > 0 && !instruction.members.is_empty(),
+        MultisigError::InvalidSpendingLimit,
+    )?;
+    for member in &instruction.members {
+        assert_that(multisig.owner_index(member).is_some(), MultisigError::InvalidSpendingLimitMember)?;
+    }
+    for (i, member) in instruction.members.iter().enumerate() {
+        assert_that(!instruction.members.iter().skip(i + 1).any(|other| other == member), MultisigError::InvalidSpendingLimit)?;
+    }
+    Ok(())
+}
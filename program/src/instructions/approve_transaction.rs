@@ -1,13 +1,24 @@
-use crate::errors::{assert_present, assert_that, MultisigError};
-use crate::state::multisig::Multisig;
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{record_threshold_reached, validate_owner_permission};
+use crate::state::multisig::{Multisig, PERMISSION_VOTE};
 use crate::state::transaction::Transaction;
-use borsh::BorshSerialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::account_info::next_account_info;
+use solana_program::clock::Clock;
 use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
 
-pub fn approve_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    msg!("invoke approve_transaction");
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ApproveTransactionInstruction {
+    // Only approves if the content hash of the transaction (see `Transaction::content_hash`) matches, binding the
+    // approval to the exact contents the approver reviewed.
+    pub expected_hash: Option<[u8; 32]>,
+}
+
+pub fn approve_transaction(program_id: &Pubkey, accounts: &[AccountInfo], instruction: ApproveTransactionInstruction) -> ProgramResult {
+    msg!("invoke approve_transaction - {:?}", instruction);
     let accounts_iter = &mut accounts.iter();
     let multisig_account = next_account_info(accounts_iter)?;
     let transaction_account = next_account_info(accounts_iter)?;
@@ -17,13 +28,28 @@ pub fn approve_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
     let mut transaction = Transaction::checked_deserialize(&transaction_account.data.borrow())?;
 
     validate(program_id, &multisig, &transaction, approver, transaction_account, multisig_account)?;
+    if let Some(expected_hash) = instruction.expected_hash {
+        assert_that(
+            transaction.content_hash(transaction_account.key)? == expected_hash,
+            MultisigError::ContentHashMismatch,
+        )?;
+    }
 
-    let owner_index =
-        assert_present(multisig.owners.iter().position(|a| a == approver.key), MultisigError::InvalidOwner)?;
+    let owner_index = validate_owner_permission(&multisig, approver.key, PERMISSION_VOTE)?;
     transaction.signers[owner_index] = true;
+    transaction.rejectors[owner_index] = false;
+    record_threshold_reached(&multisig, &mut transaction)?;
 
     transaction.serialize(&mut &mut transaction_account.data.borrow_mut()[..])?;
-    Ok(())
+
+    MultisigEvent::TransactionApproved {
+        multisig: *multisig_account.key,
+        transaction: *transaction_account.key,
+        approver: *approver.key,
+        approval_weight: transaction.approval_weight(&multisig),
+        threshold_reached_at: transaction.threshold_reached_at,
+    }
+    .emit()
 }
 
 fn validate(
@@ -40,5 +66,7 @@ fn validate(
     assert_that(approver.is_signer, MultisigError::ApproverNotSigner)?;
     assert_that(transaction_account.is_writable, MultisigError::ImmutableTransactionAccount)?;
     assert_that(transaction.multisig == *multisig_account.key, MultisigError::InvalidTransactionAccount)?;
+    assert_that(!transaction.is_expired(&Clock::get()?), MultisigError::TransactionExpired)?;
+    assert_that(!transaction.rejected, MultisigError::TransactionRejected)?;
     Ok(())
 }
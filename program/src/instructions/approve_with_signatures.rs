@@ -0,0 +1,123 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::record_threshold_reached;
+use crate::state::multisig::{Multisig, PERMISSION_VOTE};
+use crate::state::transaction::Transaction;
+use borsh::BorshSerialize;
+use solana_instructions_sysvar::load_instruction_at_checked;
+use solana_program::account_info::next_account_info;
+use solana_program::clock::Clock;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+use solana_sdk_ids::ed25519_program;
+
+// The ed25519 program instruction data starts with the number of signatures and a padding byte, followed by the
+// offsets of each signature, its public key and its message (each a u16 offset plus a u16 instruction index, except
+// for the message which also has a u16 size in between).
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+// instruction index referring to the ed25519 instruction itself
+const ED25519_SAME_INSTRUCTION: u16 = u16::MAX;
+
+/// Approves a transaction on behalf of all owners who signed its content hash (see `Transaction::content_hash`)
+/// off-chain. The signatures are verified by ed25519 program instructions in the same transaction, which are read from
+/// the instructions sysvar, so the owners do not need to sign the Solana transaction itself and anyone can relay it.
+/// As a signature can be replayed by anyone, it is ignored for owners who have since rejected the transaction or revoked
+/// an approval - they have to approve with `approve` again.
+pub fn approve_with_signatures(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("invoke approve_with_signatures");
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let transaction_account = next_account_info(accounts_iter)?;
+    let instructions_sysvar = next_account_info(accounts_iter)?;
+
+    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    let mut transaction = Transaction::checked_deserialize(&transaction_account.data.borrow())?;
+
+    validate(program_id, &multisig, &transaction, transaction_account, multisig_account, instructions_sysvar)?;
+
+    let content_hash = transaction.content_hash(transaction_account.key)?;
+    let mut approvers: Vec<Pubkey> = Vec::new();
+    // signatures of anyone else (or over other messages) may serve other purposes and are ignored
+    for signer in ed25519_signers(instructions_sysvar, &content_hash)? {
+        if let Some(owner_index) = multisig.owner_index(&signer) {
+            let withdrawn = transaction.rejectors[owner_index] || transaction.revokers[owner_index];
+            if multisig.owners[owner_index].has_permission(PERMISSION_VOTE) && !withdrawn && !approvers.contains(&signer) {
+                transaction.signers[owner_index] = true;
+                approvers.push(signer);
+            }
+        }
+    }
+    assert_that(!approvers.is_empty(), MultisigError::MissingSignatureApproval)?;
+    record_threshold_reached(&multisig, &mut transaction)?;
+
+    transaction.serialize(&mut &mut transaction_account.data.borrow_mut()[..])?;
+
+    for approver in approvers {
+        MultisigEvent::TransactionApproved {
+            multisig: *multisig_account.key,
+            transaction: *transaction_account.key,
+            approver,
+            approval_weight: transaction.approval_weight(&multisig),
+            threshold_reached_at: transaction.threshold_reached_at,
+        }
+        .emit()?;
+    }
+    Ok(())
+}
+
+/// Collects the public keys of all signatures over the given message that are verified by ed25519 program
+/// instructions of the current transaction. Only signatures whose public key and message are contained in the ed25519
+/// instruction itself are considered.
+fn ed25519_signers(instructions_sysvar: &AccountInfo, message: &[u8]) -> Result<Vec<Pubkey>, ProgramError> {
+    let mut signers = Vec::new();
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+        if instruction.program_id != ed25519_program::ID {
+            continue;
+        }
+        let data = &instruction.data;
+        let signature_count = data.first().copied().unwrap_or(0) as usize;
+        for offsets in (0..signature_count).filter_map(|i| {
+            let start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_SIZE;
+            data.get(start..start + ED25519_OFFSETS_SIZE)
+        }) {
+            let read_u16 = |position: usize| u16::from_le_bytes([offsets[position], offsets[position + 1]]);
+            let (signature_instruction, public_key_offset, public_key_instruction) = (read_u16(2), read_u16(4), read_u16(6));
+            let (message_offset, message_size, message_instruction) = (read_u16(8), read_u16(10), read_u16(12));
+            if [signature_instruction, public_key_instruction, message_instruction].iter().any(|&ix| ix != ED25519_SAME_INSTRUCTION) {
+                continue;
+            }
+            let public_key = data.get(public_key_offset as usize..public_key_offset as usize + 32);
+            let signed_message = data.get(message_offset as usize..message_offset as usize + message_size as usize);
+            if let (Some(public_key), Some(signed_message)) = (public_key, signed_message) {
+                if signed_message == message {
+                    signers.push(Pubkey::try_from(public_key).map_err(|_| ProgramError::InvalidInstructionData)?);
+                }
+            }
+        }
+    }
+    Ok(signers)
+}
+
+fn validate(
+    program_id: &Pubkey,
+    multisig: &Multisig,
+    transaction: &Transaction,
+    transaction_account: &AccountInfo,
+    multisig_account: &AccountInfo,
+    instructions_sysvar: &AccountInfo,
+) -> ProgramResult {
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(*program_id == *transaction_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(multisig.owner_set_seqno == transaction.owner_set_seqno, MultisigError::InvalidOwnerSetSequenceNumber)?;
+    assert_that(transaction_account.is_writable, MultisigError::ImmutableTransactionAccount)?;
+    assert_that(transaction.multisig == *multisig_account.key, MultisigError::InvalidTransactionAccount)?;
+    assert_that(*instructions_sysvar.key == solana_instructions_sysvar::ID, ProgramError::UnsupportedSysvar)?;
+    assert_that(!transaction.is_expired(&Clock::get()?), MultisigError::TransactionExpired)?;
+    assert_that(!transaction.rejected, MultisigError::TransactionRejected)?;
+    Ok(())
+}
@@ -1,10 +1,15 @@
-use crate::errors::{assert_that, MultisigError};
-use crate::instructions::common::close_account;
-use crate::state::multisig::Multisig;
+use crate::errors::{assert_present, assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{close_account, remove_pending_transaction, replace_with_receipt, resize_account, validate_owner_permission};
+use crate::state::multisig::{Multisig, PERMISSION_VOTE};
 use crate::state::transaction::Transaction;
+use crate::state::transaction_receipt::TransactionReceipt;
+use borsh::BorshSerialize;
 use solana_program::account_info::next_account_info;
+use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+use solana_sdk_ids::system_program;
 
 pub fn cancel_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     msg!("invoke cancel_transaction");
@@ -14,10 +19,84 @@ pub fn cancel_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> Prog
     let transaction_account = next_account_info(accounts_iter)?;
     let refundee = next_account_info(accounts_iter)?;
     let executor = next_account_info(accounts_iter)?;
+    // only required to record cancel votes of a larger owner set than the one the transaction was proposed for
+    let payer = next_account_info(accounts_iter).ok();
+    let system_program = next_account_info(accounts_iter).ok();
 
     validate(program_id, multisig_account, transaction_account, refundee, executor)?;
 
-    close_account(transaction_account, refundee)
+    let mut multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    let mut transaction = Transaction::checked_deserialize(&transaction_account.data.borrow())?;
+
+    // The proposer may always withdraw their own proposal, and a transaction proposed for an outdated owner set
+    // can never be executed anyway, so neither needs the cancel votes of other owners. Neither applies once the
+    // execution has started, as the remaining instructions can still be executed.
+    if transaction.executed_up_to == 0 && (transaction.proposer == *executor.key || transaction.owner_set_seqno < multisig.owner_set_seqno) {
+        return cancel(multisig_account, &mut multisig, transaction_account, &transaction, refundee, executor);
+    }
+
+    let owner_index = validate_owner_permission(&multisig, executor.key, PERMISSION_VOTE)?;
+    // votes are recorded by owner index, so those of an earlier owner set (of a partially executed transaction) no
+    // longer count
+    if transaction.cancellers_owner_set_seqno != multisig.owner_set_seqno {
+        transaction.cancellers = vec![false; multisig.owners.len()];
+        transaction.cancellers_owner_set_seqno = multisig.owner_set_seqno;
+    }
+    transaction.cancellers[owner_index] = true;
+    if transaction.cancellation_count() >= multisig.cancel_threshold {
+        return cancel(multisig_account, &mut multisig, transaction_account, &transaction, refundee, executor);
+    }
+
+    if transaction.len() > transaction_account.data_len() {
+        let (payer, system_program) = assert_present(payer.zip(system_program), ProgramError::NotEnoughAccountKeys)?;
+        assert_that(system_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
+        resize_account(transaction_account, payer, system_program, transaction.len())?;
+    } else {
+        transaction_account.resize(transaction.len())?;
+    }
+    transaction.serialize(&mut &mut transaction_account.data.borrow_mut()[..])?;
+
+    MultisigEvent::CancelVoted {
+        multisig: *multisig_account.key,
+        transaction: *transaction_account.key,
+        canceller: *executor.key,
+        cancellation_count: transaction.cancellation_count(),
+    }
+    .emit()
+}
+
+/// Closes the transaction account, unless some of its instructions have already been executed: then it is replaced by
+/// a receipt as proof of those (the receipts of the instructions that were not executed have slot 0).
+fn cancel(
+    multisig_account: &AccountInfo,
+    multisig: &mut Multisig,
+    transaction_account: &AccountInfo,
+    transaction: &Transaction,
+    refundee: &AccountInfo,
+    executor: &AccountInfo,
+) -> ProgramResult {
+    remove_pending_transaction(multisig_account, multisig)?;
+    if transaction.executed_up_to > 0 {
+        let receipt = TransactionReceipt {
+            discriminator: TransactionReceipt::DISCRIMINATOR,
+            version: TransactionReceipt::VERSION,
+            multisig: transaction.multisig,
+            transaction_index: transaction.transaction_index,
+            content_hash: transaction.content_hash(transaction_account.key)?,
+            executor: *executor.key,
+            receipts: transaction.receipts.clone(),
+        };
+        replace_with_receipt(transaction_account, refundee, &receipt)?;
+    } else {
+        close_account(transaction_account, refundee)?;
+    }
+
+    MultisigEvent::TransactionCancelled {
+        multisig: *multisig_account.key,
+        transaction: *transaction_account.key,
+        canceller: *executor.key,
+    }
+    .emit()
 }
 
 fn validate(
@@ -33,12 +112,15 @@ fn validate(
     let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
     let transaction = Transaction::checked_deserialize(&transaction_account.data.borrow())?;
 
-    assert_that(executor.is_signer && multisig.owners.contains(executor.key), MultisigError::InvalidExecutor)?;
+    assert_that(executor.is_signer && multisig.owner_index(executor.key).is_some(), MultisigError::InvalidExecutor)?;
     assert_that(multisig.owner_set_seqno >= transaction.owner_set_seqno, MultisigError::InvalidOwnerSetSequenceNumber)?;
     assert_that(transaction.multisig == *multisig_account.key, MultisigError::InvalidTransactionAccount)?;
+    assert_that(multisig_account.is_writable, MultisigError::ImmutableMultisigAccount)?;
     assert_that(transaction_account.is_writable, MultisigError::ImmutableTransactionAccount)?;
     assert_that(refundee.key != transaction_account.key, MultisigError::InvalidRefundeeAccount)?;
     assert_that(refundee.is_writable, MultisigError::ImmutableRefundeeAccount)?;
+    // the rent goes back to whoever put it up, not to an account of the canceller's choosing
+    assert_that(transaction.rent_payer == *refundee.key, MultisigError::RentPayerMismatch)?;
 
     Ok(())
 }
@@ -10,7 +10,7 @@ use crate::errors::{assert_that, MultisigError};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct ChangeThresholdInstruction {
-    pub threshold: u8,
+    pub threshold: u16,
 }
 
 pub fn change_threshold(
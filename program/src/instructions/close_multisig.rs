@@ -0,0 +1,53 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{replace_account_data, validate_signer};
+use crate::state::multisig::Multisig;
+use solana_program::account_info::next_account_info;
+use solana_program::program::invoke;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+use solana_sdk_ids::system_program;
+use solana_system_interface::instruction as system_instruction;
+
+/// Closes the multisig account and transfers the remaining lamports of its signer PDA (vault 0) to the recipient. Only
+/// the lamports of vault 0 are swept - tokens and other vaults have to be moved by transactions executed beforehand, as
+/// they are inaccessible once the multisig is gone. Spending limits have to be removed beforehand as well, so that
+/// their rent is not lost. The receipts of executed transactions are kept as proof of their execution.
+///
+/// The account is not deleted but shrunk to a tombstone (see `Multisig::CLOSED_DISCRIMINATOR`), as re-creating a
+/// multisig at its address would hand its vaults (and any authorities they still hold) to a new owner set.
+pub fn close_multisig(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("invoke close_multisig");
+
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let multisig_signer = next_account_info(accounts_iter)?;
+    let recipient = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(multisig_account.is_writable, MultisigError::ImmutableMultisigAccount)?;
+    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    validate_signer(multisig_signer, multisig_account, &multisig, program_id)?;
+    // the execution of the transaction closing the multisig is not pending anymore at this point
+    assert_that(multisig.pending_transactions == 0, MultisigError::PendingTransactions)?;
+    assert_that(multisig.spending_limits == 0, MultisigError::ActiveSpendingLimits)?;
+    assert_that(
+        recipient.key != multisig_account.key && recipient.key != multisig_signer.key,
+        MultisigError::InvalidRefundeeAccount,
+    )?;
+    assert_that(recipient.is_writable, MultisigError::ImmutableRefundeeAccount)?;
+    assert_that(system_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
+
+    let lamports = multisig_signer.lamports();
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(multisig_signer.key, recipient.key, lamports),
+            &[multisig_signer.clone(), recipient.clone(), system_program.clone()],
+        )?;
+    }
+    replace_account_data(multisig_account, recipient, &[Multisig::CLOSED_DISCRIMINATOR, Multisig::VERSION])?;
+
+    MultisigEvent::MultisigClosed { multisig: *multisig_account.key, recipient: *recipient.key }.emit()
+}
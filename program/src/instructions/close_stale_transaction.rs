@@ -0,0 +1,62 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{close_account, remove_pending_transaction};
+use crate::state::multisig::Multisig;
+use crate::state::transaction::Transaction;
+use solana_program::account_info::next_account_info;
+use solana_program::clock::Clock;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+
+/// Closes a transaction that can no longer be executed, because it was rejected, has expired or was proposed for an
+/// outdated owner set. As the rent is always refunded to the rent payer of the transaction, this can be invoked by
+/// anyone.
+pub fn close_stale_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("invoke close_stale_transaction");
+
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let transaction_account = next_account_info(accounts_iter)?;
+    let rent_payer = next_account_info(accounts_iter)?;
+
+    let mut multisig = validate(program_id, multisig_account, transaction_account, rent_payer)?;
+    remove_pending_transaction(multisig_account, &mut multisig)?;
+    close_account(transaction_account, rent_payer)?;
+
+    MultisigEvent::StaleTransactionClosed {
+        multisig: *multisig_account.key,
+        transaction: *transaction_account.key,
+    }
+    .emit()
+}
+
+fn validate(
+    program_id: &Pubkey,
+    multisig_account: &AccountInfo,
+    transaction_account: &AccountInfo,
+    rent_payer: &AccountInfo,
+) -> Result<Multisig, ProgramError> {
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(*program_id == *transaction_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+
+    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    let transaction = Transaction::checked_deserialize(&transaction_account.data.borrow())?;
+
+    assert_that(transaction.multisig == *multisig_account.key, MultisigError::InvalidTransactionAccount)?;
+    assert_that(multisig_account.is_writable, MultisigError::ImmutableMultisigAccount)?;
+    assert_that(transaction_account.is_writable, MultisigError::ImmutableTransactionAccount)?;
+    assert_that(transaction.rent_payer == *rent_payer.key, MultisigError::RentPayerMismatch)?;
+    assert_that(rent_payer.is_writable, MultisigError::ImmutableRefundeeAccount)?;
+    // transactions proposed for an outdated owner set can never be executed
+    let outdated = transaction.owner_set_seqno != multisig.owner_set_seqno;
+    assert_that(
+        transaction.rejected || transaction.is_expired(&Clock::get()?) || outdated,
+        MultisigError::TransactionNotStale,
+    )?;
+    // a transaction that is partially executed may still be completed, regardless of its expiry or owner set
+    assert_that(transaction.executed_up_to == 0, MultisigError::TransactionNotStale)?;
+
+    Ok(multisig)
+}
@@ -0,0 +1,34 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::close_account;
+use crate::state::transaction_buffer::TransactionBuffer;
+use solana_program::account_info::next_account_info;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+
+/// Closes a transaction buffer that is not going to be finalized, refunding its rent to the creator. Only the creator
+/// has to sign, so that a buffer can still be closed after its creator has been removed from the owners.
+pub fn close_transaction_buffer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("invoke close_transaction_buffer");
+
+    let accounts_iter = &mut accounts.iter();
+    let transaction_buffer_account = next_account_info(accounts_iter)?;
+    let creator = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *transaction_buffer_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    let transaction_buffer = TransactionBuffer::checked_deserialize(&transaction_buffer_account.data.borrow())?;
+    assert_that(
+        creator.is_signer && transaction_buffer.creator == *creator.key,
+        MultisigError::InvalidTransactionBufferCreator,
+    )?;
+    assert_that(creator.is_writable, MultisigError::ImmutableRefundeeAccount)?;
+
+    close_account(transaction_buffer_account, creator)?;
+
+    MultisigEvent::TransactionBufferClosed {
+        multisig: transaction_buffer.multisig,
+        transaction_buffer: *transaction_buffer_account.key,
+        creator: *creator.key,
+    }
+    .emit()
+}
@@ -1,12 +1,22 @@
 use borsh::BorshSerialize;
 use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::Instruction;
 use solana_program::pubkey::{Pubkey, PUBKEY_BYTES};
 use solana_program::{msg};
-use solana_sdk_ids::system_program;
+use solana_program::program::{invoke, invoke_signed};
+use solana_sdk_ids::{address_lookup_table, system_program};
 use solana_program::program_error::ProgramError;
-use crate::errors::{assert_that, assert_unique_owners, MultisigError};
-use crate::state::multisig::Multisig;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+use solana_system_interface::instruction as system_instruction;
+use crate::errors::{assert_present, assert_that, assert_unique_owners, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::propose_config_transaction::{resolve_config_actions, ConfigAction};
+use crate::state::multisig::{count_voters, total_weight, Multisig, Owner, PERMISSION_ALL, PERMISSION_EXECUTE, PERMISSION_PROPOSE, PERMISSION_VOTE};
+use crate::state::transaction::Transaction;
+use crate::state::transaction_receipt::TransactionReceipt;
 
 pub fn close_account(account: &AccountInfo, refundee: &AccountInfo) -> ProgramResult {
     let lamports = account.lamports();
@@ -20,31 +30,197 @@ pub fn close_account(account: &AccountInfo, refundee: &AccountInfo) -> ProgramRe
     Ok(())
 }
 
-pub fn execute_change_threshold(multisig_account: &&AccountInfo, multisig_data: &mut Multisig, threshold: u8) -> ProgramResult {
+/// Shrinks the transaction account to the given receipt, refunding the rent that is no longer needed.
+pub fn replace_with_receipt(transaction_account: &AccountInfo, refundee: &AccountInfo, receipt: &TransactionReceipt) -> ProgramResult {
+    replace_account_data(transaction_account, refundee, &borsh::to_vec(receipt)?)
+}
+
+/// Replaces the data of an account with the given (smaller) data, refunding the rent that is no longer needed.
+pub fn replace_account_data(account: &AccountInfo, refundee: &AccountInfo, data: &[u8]) -> ProgramResult {
+    let refund = account.lamports().saturating_sub(Rent::get()?.minimum_balance(data.len()));
+    **account.lamports.borrow_mut() -= refund;
+    **refundee.lamports.borrow_mut() = refundee.lamports()
+        .checked_add(refund)
+        .ok_or(MultisigError::AccountCloseFailure)?;
+    account.resize(data.len())?;
+    account.data.borrow_mut().copy_from_slice(data);
+    Ok(())
+}
+
+/// Removes a transaction from the pending transactions of its multisig, which has to happen whenever a transaction
+/// account is closed.
+pub fn remove_pending_transaction(multisig_account: &AccountInfo, multisig: &mut Multisig) -> ProgramResult {
+    multisig.pending_transactions = multisig.pending_transactions.checked_sub(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    multisig.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Creates a program owned account at the given PDA address. This also succeeds if someone transferred lamports to the
+/// address upfront (which would make a plain `create_account` fail), so that nobody can block the address.
+pub fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    program_id: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, required_lamports, space as u64, program_id),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[signer_seeds],
+        );
+    }
+
+    let missing_lamports = required_lamports.saturating_sub(account.lamports());
+    if missing_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, missing_lamports),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, program_id),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+/// Resizes a program owned account, topping up its rent exemption from the payer if it grows.
+pub fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+) -> ProgramResult {
+    let missing_lamports = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if missing_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, missing_lamports),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    account.resize(space)
+}
+
+pub fn execute_change_threshold(multisig_account: &&AccountInfo, multisig_data: &mut Multisig, threshold: u16) -> ProgramResult {
     multisig_data.threshold = threshold;
     multisig_data.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
-    Ok(())
+    MultisigEvent::ThresholdChanged { multisig: *multisig_account.key, threshold }.emit()
 }
 
-pub fn execute_set_owners(multisig_account: &&AccountInfo, multisig_data: &mut Multisig, owners: Vec<Pubkey>) -> ProgramResult {
+pub fn execute_change_cancel_threshold(multisig_account: &&AccountInfo, multisig_data: &mut Multisig, cancel_threshold: u8) -> ProgramResult {
+    multisig_data.cancel_threshold = cancel_threshold;
+    multisig_data.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+    MultisigEvent::CancelThresholdChanged { multisig: *multisig_account.key, cancel_threshold }.emit()
+}
+
+pub fn execute_change_time_lock(multisig_account: &&AccountInfo, multisig_data: &mut Multisig, time_lock_seconds: u32) -> ProgramResult {
+    multisig_data.time_lock_seconds = time_lock_seconds;
+    multisig_data.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+    MultisigEvent::TimeLockChanged { multisig: *multisig_account.key, time_lock_seconds }.emit()
+}
+
+pub fn execute_set_owners(multisig_account: &&AccountInfo, multisig_data: &mut Multisig, owners: Vec<Owner>) -> ProgramResult {
     // the padding ensures the multisig account size stays constant when decreasing the number of owners
-    // (so that it can be re-expanded to the original number of owners later)
-    let padding_len = multisig_data.padding.len() +
-        PUBKEY_BYTES * (multisig_data.owners.len() - owners.len());
+    // (so that it can be re-expanded to the original number of owners later), growing the owner list beyond that
+    // requires the account to be resized first (see `ensure_owner_capacity`)
+    let padding_len = owner_capacity(multisig_data).saturating_sub(Owner::LEN * owners.len());
     multisig_data.padding = vec![0; padding_len];
     multisig_data.owners = owners;
     multisig_data.owner_set_seqno += 1;
-    if (multisig_data.owners.len() as u8) < multisig_data.threshold {
-        multisig_data.threshold = multisig_data.owners.len() as u8;
+    let total_weight = multisig_data.total_weight();
+    if total_weight < multisig_data.threshold as u32 {
+        multisig_data.threshold = total_weight as u16;
+    }
+    let voter_count = multisig_data.voter_count();
+    if voter_count < multisig_data.cancel_threshold {
+        multisig_data.cancel_threshold = voter_count;
     }
     multisig_data.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+    MultisigEvent::OwnersChanged {
+        multisig: *multisig_account.key,
+        owners: multisig_data.owners.clone(),
+        owner_set_seqno: multisig_data.owner_set_seqno,
+        threshold: multisig_data.threshold,
+        cancel_threshold: multisig_data.cancel_threshold,
+    }
+    .emit()
+}
+
+/// Applies the actions of an executed config transaction to the multisig. They are resolved again, as the thresholds
+/// may have changed since the transaction was proposed.
+pub fn execute_config_actions(multisig_account: &&AccountInfo, multisig_data: &mut Multisig, actions: &[ConfigAction]) -> ProgramResult {
+    let change = resolve_config_actions(multisig_data, actions)?;
+    if let Some(owners) = change.owners {
+        execute_set_owners(multisig_account, multisig_data, owners)?;
+    }
+    if let Some(threshold) = change.threshold {
+        execute_change_threshold(multisig_account, multisig_data, threshold)?;
+    }
+    if let Some(time_lock_seconds) = change.time_lock_seconds {
+        execute_change_time_lock(multisig_account, multisig_data, time_lock_seconds)?;
+    }
     Ok(())
 }
 
+pub fn owner_capacity(multisig: &Multisig) -> usize {
+    Owner::LEN * multisig.owners.len() + multisig.padding.len()
+}
+
+/// Grows the multisig account if the given number of owners does not fit into its current size (including the
+/// padding), topping up its rent exemption from the payer (e.g. the multisig signer itself).
+pub fn ensure_owner_capacity<'a>(
+    multisig_account: &AccountInfo<'a>,
+    multisig: &Multisig,
+    owner_count: usize,
+    payer: Option<&AccountInfo<'a>>,
+    system_program: Option<&AccountInfo<'a>>,
+) -> ProgramResult {
+    let capacity = owner_capacity(multisig);
+    if Owner::LEN * owner_count <= capacity {
+        return Ok(());
+    }
+    let (payer, system_program) = assert_present(payer.zip(system_program), MultisigError::TooManyOwners)?;
+    assert_that(system_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
+    resize_account(multisig_account, payer, system_program, multisig.len() - capacity + Owner::LEN * owner_count)
+}
+
+/// Starts the time lock of the given transaction if it has just reached the approval threshold of the multisig.
+pub fn record_threshold_reached(multisig: &Multisig, transaction: &mut Transaction) -> ProgramResult {
+    if transaction.threshold_reached_at == 0 && transaction.approval_weight(multisig) >= multisig.threshold as u32 {
+        transaction.threshold_reached_at = Clock::get()?.unix_timestamp;
+    }
+    Ok(())
+}
+
+// size of the metadata preceding the addresses in an address lookup table account
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// Reads the address at the given index from an address lookup table. Lookup tables are append-only and cannot be
+/// re-created at the same address, so an address read once never changes.
+pub fn lookup_table_address(address_lookup_table: &AccountInfo, index: u8) -> Result<Pubkey, ProgramError> {
+    assert_that(
+        *address_lookup_table.owner == address_lookup_table::ID,
+        MultisigError::InvalidAddressLookupTable,
+    )?;
+    let start = LOOKUP_TABLE_META_SIZE + index as usize * PUBKEY_BYTES;
+    let data = address_lookup_table.data.borrow();
+    let address = data.get(start..start + PUBKEY_BYTES).ok_or(MultisigError::InvalidAddressLookupTable)?;
+    Ok(Pubkey::try_from(address).map_err(|_| MultisigError::InvalidAddressLookupTable)?)
+}
+
 pub fn validate_pda(multisig_signer: &AccountInfo, multisig_account: &AccountInfo, nonce: u8, program_id: &Pubkey) -> ProgramResult {
     let pda_address = Pubkey::create_program_address(
         &[multisig_account.key.as_ref(), &[nonce][..]],
-        &program_id,
+        program_id,
     ).map_err(|err| {
         msg!("could not derive pda address from multisig {} and nonce {}: {}", multisig_account.key, nonce, err);
         ProgramError::InvalidSeeds
@@ -54,6 +230,40 @@ pub fn validate_pda(multisig_signer: &AccountInfo, multisig_account: &AccountInf
     Ok(())
 }
 
+/// Checks that the given signer is the PDA of the given vault and returns the bump (the nonce for vault 0) needed to
+/// sign for it.
+pub fn validate_vault(vault_signer: &AccountInfo, multisig_account: &AccountInfo, multisig: &Multisig, vault_index: u8, program_id: &Pubkey) -> Result<u8, ProgramError> {
+    if vault_index == 0 {
+        validate_pda(vault_signer, multisig_account, multisig.nonce, program_id)?;
+        return Ok(multisig.nonce);
+    }
+    let (vault_address, bump) = Multisig::find_vault_address(multisig_account.key, vault_index, program_id);
+    assert_that(*vault_signer.key == vault_address, ProgramError::InvalidSeeds)?;
+    Ok(bump)
+}
+
+/// Invokes the given instruction with the signature of a vault of the multisig (see `validate_vault` for the bump) and
+/// any additional PDA signers.
+pub fn invoke_signed_by_vault(
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+    multisig: &Pubkey,
+    vault_index: u8,
+    vault_bump: u8,
+    additional_signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let vault_index_seed = [vault_index];
+    let vault_bump_seed = [vault_bump];
+    let vault_seeds: Vec<&[u8]> = if vault_index == 0 {
+        vec![multisig.as_ref(), &vault_bump_seed]
+    } else {
+        vec![multisig.as_ref(), Multisig::VAULT_SEED_PREFIX, &vault_index_seed, &vault_bump_seed]
+    };
+    let mut signer_seeds: Vec<&[&[u8]]> = vec![&vault_seeds];
+    signer_seeds.extend_from_slice(additional_signer_seeds);
+    invoke_signed(instruction, accounts, &signer_seeds)
+}
+
 pub fn validate_signer(multisig_signer: &AccountInfo, multisig_account: &AccountInfo, multisig: &Multisig, program_id: &Pubkey) -> ProgramResult {
     validate_pda(multisig_signer, multisig_account, multisig.nonce, program_id)?;
     assert_that(multisig_signer.is_signer, MultisigError::MultisigSignerNotSigner)?;
@@ -61,16 +271,39 @@ pub fn validate_signer(multisig_signer: &AccountInfo, multisig_account: &Account
     Ok(())
 }
 
-pub fn validate_owners(multisig: &Multisig, owners: &[Pubkey]) -> ProgramResult {
+pub fn validate_owners(owners: &[Owner]) -> ProgramResult {
     assert_unique_owners(owners)?;
     assert_that(!owners.is_empty(), MultisigError::NotEnoughOwners)?;
-    // Increasing the number of owners requires reallocation of space in the data account.
-    // This requires a signer to pay the fees for more space, but the instruction will be executed by the multisig.
-    assert_that(owners.len() * PUBKEY_BYTES <= multisig.owners.len() * PUBKEY_BYTES + multisig.padding.len(), MultisigError::TooManyOwners)?;
+    validate_permissions(owners)?;
+    Ok(())
+}
+
+/// Ensures that every owner holds at least one known permission (voting owners with a non-zero weight) and that no
+/// permission is left unassigned, as the multisig could otherwise never propose, approve or execute a transaction again.
+pub fn validate_permissions(owners: &[Owner]) -> ProgramResult {
+    for owner in owners {
+        assert_that(owner.permissions != 0 && owner.permissions & !PERMISSION_ALL == 0, MultisigError::InvalidPermissions)?;
+        assert_that(!owner.has_permission(PERMISSION_VOTE) || owner.weight > 0, MultisigError::InvalidWeight)?;
+    }
+    for permission in [PERMISSION_PROPOSE, PERMISSION_VOTE, PERMISSION_EXECUTE] {
+        assert_that(owners.iter().any(|owner| owner.has_permission(permission)), MultisigError::InvalidPermissions)?;
+    }
+    Ok(())
+}
+
+/// Returns the index of the given owner after checking that they hold the given permission.
+pub fn validate_owner_permission(multisig: &Multisig, key: &Pubkey, permission: u8) -> Result<usize, ProgramError> {
+    let owner_index = assert_present(multisig.owner_index(key), MultisigError::InvalidOwner)?;
+    assert_that(multisig.owners[owner_index].has_permission(permission), MultisigError::MissingPermission)?;
+    Ok(owner_index)
+}
+
+pub fn validate_threshold(threshold: u16, owners: &[Owner]) -> ProgramResult {
+    assert_that(threshold > 0 && threshold as u32 <= total_weight(owners), MultisigError::InvalidThreshold)?;
     Ok(())
 }
 
-pub fn validate_threshold(threshold: u8, owners: &Vec<Pubkey>) -> ProgramResult {
-    assert_that(threshold > 0 && threshold <= owners.len() as u8, MultisigError::InvalidThreshold)?;
+pub fn validate_cancel_threshold(cancel_threshold: u8, owners: &[Owner]) -> ProgramResult {
+    assert_that(cancel_threshold > 0 && cancel_threshold <= count_voters(owners), MultisigError::InvalidCancelThreshold)?;
     Ok(())
 }
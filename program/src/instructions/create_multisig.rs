@@ -1,6 +1,7 @@
-use crate::errors::{assert_that, assert_unique_owners, MultisigError};
-use crate::instructions::common::validate_pda;
-use crate::state::multisig::Multisig;
+use crate::errors::{assert_that, assert_unique_owners};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{validate_cancel_threshold, validate_pda, validate_permissions, validate_threshold};
+use crate::state::multisig::{Multisig, Owner};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::account_info::next_account_info;
 use solana_program::program::invoke;
@@ -16,9 +17,12 @@ use solana_system_interface::instruction as system_instruction;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct CreateMultisigInstruction {
-    pub owners: Vec<Pubkey>,
-    pub threshold: u8,
+    pub owners: Vec<Owner>,
+    pub threshold: u16,
     pub nonce: u8,
+    pub time_lock_seconds: u32,
+    // The number of owners that have to vote to cancel a transaction (None defaults to a single owner).
+    pub cancel_threshold: Option<u8>,
 }
 
 pub fn create_multisig(
@@ -33,13 +37,21 @@ pub fn create_multisig(
     let payer = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
 
-    validate(program_id, multisig_account, multisig_signer, system_program, &instruction)?;
+    let cancel_threshold = instruction.cancel_threshold.unwrap_or(1);
+    validate(program_id, multisig_account, multisig_signer, system_program, &instruction, cancel_threshold)?;
 
     let multisig_data = Multisig {
+        discriminator: Multisig::DISCRIMINATOR,
+        version: Multisig::VERSION,
         owners: instruction.owners,
         threshold: instruction.threshold,
+        cancel_threshold,
         nonce: instruction.nonce,
         owner_set_seqno: 0,
+        time_lock_seconds: instruction.time_lock_seconds,
+        transaction_index: 0,
+        pending_transactions: 0,
+        spending_limits: 0,
         padding: vec![],
     };
     invoke(
@@ -53,7 +65,15 @@ pub fn create_multisig(
         &[payer.clone(), multisig_account.clone(), system_program.clone(), ],
     )?;
     multisig_data.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
-    Ok(())
+
+    MultisigEvent::MultisigCreated {
+        multisig: *multisig_account.key,
+        owners: multisig_data.owners,
+        threshold: multisig_data.threshold,
+        cancel_threshold: multisig_data.cancel_threshold,
+        time_lock_seconds: multisig_data.time_lock_seconds,
+    }
+    .emit()
 }
 
 fn validate(
@@ -62,13 +82,13 @@ fn validate(
     multisig_signer: &AccountInfo,
     system_program: &AccountInfo,
     instruction: &CreateMultisigInstruction,
+    cancel_threshold: u8,
 ) -> ProgramResult {
     assert_that(system_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
     assert_unique_owners(&instruction.owners)?;
-    assert_that(
-        instruction.threshold > 0 && instruction.threshold <= instruction.owners.len() as u8,
-        MultisigError::InvalidThreshold,
-    )?;
+    validate_threshold(instruction.threshold, &instruction.owners)?;
+    validate_cancel_threshold(cancel_threshold, &instruction.owners)?;
+    validate_permissions(&instruction.owners)?;
     validate_pda(multisig_signer, multisig, instruction.nonce, program_id)?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,106 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{create_pda_account, validate_owner_permission};
+use crate::instructions::propose_transaction::TransactionExpiry;
+use crate::state::multisig::{Multisig, PERMISSION_PROPOSE};
+use crate::state::transaction::Transaction;
+use crate::state::transaction_buffer::TransactionBuffer;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::account_info::next_account_info;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+use solana_sdk_ids::system_program;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CreateTransactionBufferInstruction {
+    pub buffer_index: u8,
+    pub vault_index: u8,
+    pub ephemeral_signers: u8,
+    pub expiry: Option<TransactionExpiry>,
+    pub address_lookup_tables: Vec<Pubkey>,
+    // a short note on the purpose of the transaction (at most `Transaction::MAX_MEMO_LEN` bytes)
+    pub memo: Option<String>,
+    // the hash of an off-chain document describing the transaction
+    pub description_hash: Option<[u8; 32]>,
+    // SHA-256 hash of the complete buffer
+    pub final_hash: [u8; 32],
+    pub final_size: u32,
+    // the first chunk of the borsh serialized instructions
+    pub buffer: Vec<u8>,
+}
+
+/// Starts the upload of a transaction whose instructions do not fit into a single `ProposeTransaction` instruction.
+pub fn create_transaction_buffer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: CreateTransactionBufferInstruction,
+) -> ProgramResult {
+    msg!("invoke create_transaction_buffer - {:?}", instruction);
+
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let transaction_buffer_account = next_account_info(accounts_iter)?;
+    let creator = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(system_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
+    assert_that(creator.is_signer, MultisigError::ProposerNotSigner)?;
+    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    validate_owner_permission(&multisig, creator.key, PERMISSION_PROPOSE)?;
+    assert_that(
+        instruction.final_size > 0 && instruction.buffer.len() <= instruction.final_size as usize,
+        MultisigError::InvalidTransactionBufferSize,
+    )?;
+    assert_that(
+        instruction.memo.as_ref().is_none_or(|memo| memo.len() <= Transaction::MAX_MEMO_LEN),
+        MultisigError::MemoTooLong,
+    )?;
+
+    let (transaction_buffer_address, bump) =
+        TransactionBuffer::find_address(multisig_account.key, creator.key, instruction.buffer_index, program_id);
+    assert_that(*transaction_buffer_account.key == transaction_buffer_address, ProgramError::InvalidSeeds)?;
+
+    let transaction_buffer = TransactionBuffer {
+        discriminator: TransactionBuffer::DISCRIMINATOR,
+        version: TransactionBuffer::VERSION,
+        multisig: *multisig_account.key,
+        creator: *creator.key,
+        buffer_index: instruction.buffer_index,
+        vault_index: instruction.vault_index,
+        ephemeral_signers: instruction.ephemeral_signers,
+        expiry: instruction.expiry,
+        address_lookup_tables: instruction.address_lookup_tables,
+        memo: instruction.memo,
+        description_hash: instruction.description_hash,
+        final_hash: instruction.final_hash,
+        final_size: instruction.final_size,
+        buffer: instruction.buffer,
+    };
+    create_pda_account(
+        payer,
+        transaction_buffer_account,
+        system_program,
+        transaction_buffer.len(),
+        program_id,
+        &[
+            multisig_account.key.as_ref(),
+            TransactionBuffer::SEED_PREFIX,
+            creator.key.as_ref(),
+            &[instruction.buffer_index],
+            &[bump],
+        ],
+    )?;
+    transaction_buffer.serialize(&mut &mut transaction_buffer_account.data.borrow_mut()[..])?;
+
+    MultisigEvent::TransactionBufferCreated {
+        multisig: *multisig_account.key,
+        transaction_buffer: *transaction_buffer_account.key,
+        creator: *creator.key,
+        buffer_index: transaction_buffer.buffer_index,
+        final_size: transaction_buffer.final_size,
+    }
+    .emit()
+}
@@ -1,41 +1,130 @@
-use crate::errors::{assert_that, MultisigError};
-use crate::instructions::common::{close_account, validate_pda};
-use crate::state::multisig::Multisig;
+use crate::errors::{assert_success, assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{execute_config_actions, invoke_signed_by_vault, remove_pending_transaction, replace_with_receipt, validate_owner_permission, validate_vault};
+use crate::state::multisig::{Multisig, PERMISSION_EXECUTE};
 use crate::state::transaction::Transaction;
+use crate::state::transaction_receipt::TransactionReceipt;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::account_info::next_account_info;
+use solana_program::clock::Clock;
+use solana_program::hash::hashv;
 use solana_program::instruction::Instruction;
+use solana_program::program::get_return_data;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
 
-pub fn execute_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    msg!("invoke execute_transaction");
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ExecuteTransactionInstruction {
+    // Executes at most this many of the remaining instructions, keeping the transaction account until the last one
+    // has been executed (None executes all remaining instructions).
+    pub max_instructions: Option<u16>,
+}
 
-    let validated = validate(program_id, accounts)?;
+pub fn execute_transaction(program_id: &Pubkey, accounts: &[AccountInfo], instruction: ExecuteTransactionInstruction) -> ProgramResult {
+    msg!("invoke execute_transaction - {:?}", instruction);
 
-    let signer_seeds = &[(*validated.multisig_account.key).as_ref(), &[validated.multisig.nonce]];
-    validated
+    let mut validated = validate(program_id, accounts)?;
+    let start = validated.transaction.executed_up_to as usize;
+    let remaining = validated.transaction.instructions.len() - start;
+    let end = match instruction.max_instructions {
+        Some(max_instructions) => {
+            assert_that(max_instructions > 0, MultisigError::InvalidMaxInstructions)?;
+            start + remaining.min(max_instructions as usize)
+        }
+        None => start + remaining,
+    };
+    let completes = end == validated.transaction.instructions.len();
+    if completes {
+        // before invoking the instructions, so that they see the multisig without this transaction (e.g. to close it)
+        remove_pending_transaction(validated.multisig_account, &mut validated.multisig)?;
+    }
+    // lookup tables are passed along with the accounts of the instructions
+    let address_lookup_tables = validated
         .transaction
-        .instructions
+        .address_lookup_tables
         .iter()
-        .map(|ix| {
-            let mut ix: Instruction = ix.into();
-            ix.accounts = ix
-                .accounts
-                .iter()
-                .map(|acc| {
-                    let mut acc = acc.clone();
-                    if acc.pubkey == *validated.multisig_signer.key {
-                        acc.is_signer = true;
-                    }
-                    acc
-                })
-                .collect();
-            solana_program::program::invoke_signed(&ix, accounts, &[&signer_seeds[..]])
-        })
+        .map(|table| accounts.iter().find(|account| account.key == table).ok_or(MultisigError::InvalidAddressLookupTable))
         .collect::<Result<Vec<_>, _>>()?;
+    let ephemeral_signers: Vec<(Pubkey, [u8; 1], [u8; 1])> = (0..validated.transaction.ephemeral_signers)
+        .map(|index| {
+            let (address, bump) = Transaction::find_ephemeral_signer_address(validated.transaction_account.key, index, program_id);
+            (address, [index], [bump])
+        })
+        .collect();
+    let ephemeral_signer_seeds: Vec<[&[u8]; 4]> = ephemeral_signers
+        .iter()
+        .map(|(_, index, bump)| {
+            [validated.transaction_account.key.as_ref(), Transaction::EPHEMERAL_SIGNER_SEED_PREFIX, index, bump]
+        })
+        .collect();
+    let ephemeral_signer_seeds: Vec<&[&[u8]]> = ephemeral_signer_seeds.iter().map(|seeds| &seeds[..]).collect();
 
-    close_account(validated.transaction_account, validated.refundee)
+    let slot = Clock::get()?.slot;
+    for index in start..end {
+        let mut ix: Instruction = validated.transaction.instructions[index].to_instruction(&address_lookup_tables)?;
+        ix.accounts = ix
+            .accounts
+            .iter()
+            .map(|acc| {
+                let mut acc = acc.clone();
+                if acc.pubkey == *validated.multisig_signer.key
+                    || ephemeral_signers.iter().any(|(address, _, _)| acc.pubkey == *address)
+                {
+                    acc.is_signer = true;
+                }
+                acc
+            })
+            .collect();
+        invoke_signed_by_vault(
+            &ix,
+            accounts,
+            validated.multisig_account.key,
+            validated.transaction.vault_index,
+            validated.vault_bump,
+            &ephemeral_signer_seeds,
+        )?;
+        // the runtime clears the return data before each invocation, so this is what the instruction returned
+        let receipt = &mut validated.transaction.receipts[index];
+        receipt.return_data_hash = get_return_data()
+            .map_or([0; 32], |(program_id, data)| hashv(&[program_id.as_ref(), &data]).to_bytes());
+        receipt.slot = slot;
+    }
+
+    if !completes {
+        validated.transaction.executed_up_to = assert_success(u16::try_from(end), MultisigError::TooManyInstructions)?;
+        validated.transaction.serialize(&mut &mut validated.transaction_account.data.borrow_mut()[..])?;
+        return MultisigEvent::InstructionsExecuted {
+            multisig: *validated.multisig_account.key,
+            transaction: *validated.transaction_account.key,
+            executor: *validated.executor.key,
+            executed_up_to: validated.transaction.executed_up_to,
+        }
+        .emit();
+    }
+    // config transactions have no instructions, their actions are applied directly
+    if !validated.transaction.config_actions.is_empty() {
+        execute_config_actions(&validated.multisig_account, &mut validated.multisig, &validated.transaction.config_actions)?;
+    }
+
+    let receipt = TransactionReceipt {
+        discriminator: TransactionReceipt::DISCRIMINATOR,
+        version: TransactionReceipt::VERSION,
+        multisig: validated.transaction.multisig,
+        transaction_index: validated.transaction.transaction_index,
+        content_hash: validated.transaction.content_hash(validated.transaction_account.key)?,
+        executor: *validated.executor.key,
+        receipts: validated.transaction.receipts,
+    };
+    replace_with_receipt(validated.transaction_account, validated.refundee, &receipt)?;
+
+    MultisigEvent::TransactionExecuted {
+        multisig: *validated.multisig_account.key,
+        transaction: *validated.transaction_account.key,
+        executor: *validated.executor.key,
+    }
+    .emit()
 }
 
 struct ValidatedAccounts<'a, 'b> {
@@ -43,8 +132,10 @@ struct ValidatedAccounts<'a, 'b> {
     multisig_signer: &'a AccountInfo<'b>,
     transaction_account: &'a AccountInfo<'b>,
     refundee: &'a AccountInfo<'b>,
+    executor: &'a AccountInfo<'b>,
     multisig: Multisig,
     transaction: Transaction,
+    vault_bump: u8,
 }
 
 fn validate<'a, 'b>(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<ValidatedAccounts<'a, 'b>, ProgramError>
@@ -63,18 +154,38 @@ where
 
     let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
     let transaction = Transaction::checked_deserialize(&transaction_account.data.borrow())?;
-    assert_that(executor.is_signer && multisig.owners.contains(executor.key), MultisigError::InvalidExecutor)?;
-    assert_that(multisig.owner_set_seqno == transaction.owner_set_seqno, MultisigError::InvalidOwnerSetSequenceNumber)?;
+    assert_that(executor.is_signer && multisig.owner_index(executor.key).is_some(), MultisigError::InvalidExecutor)?;
+    validate_owner_permission(&multisig, executor.key, PERMISSION_EXECUTE)?;
 
-    validate_pda(multisig_signer, multisig_account, multisig.nonce, multisig_account.owner)?;
+    let vault_bump = validate_vault(multisig_signer, multisig_account, &multisig, transaction.vault_index, multisig_account.owner)?;
 
+    assert_that(multisig_account.is_writable, MultisigError::ImmutableMultisigAccount)?;
     assert_that(transaction_account.is_writable, MultisigError::ImmutableTransactionAccount)?;
     assert_that(transaction.multisig == *multisig_account.key, MultisigError::InvalidTransactionAccount)?;
     assert_that(refundee.key != transaction_account.key, MultisigError::InvalidRefundeeAccount)?;
     assert_that(refundee.is_writable, MultisigError::ImmutableRefundeeAccount)?;
+    // the rent goes back to whoever put it up, not to an account of the executor's choosing
+    assert_that(transaction.rent_payer == *refundee.key, MultisigError::RentPayerMismatch)?;
 
-    let approval_count = transaction.signers.iter().filter(|&did_sign| *did_sign).count() as u8;
-    assert_that(approval_count >= multisig.threshold, MultisigError::NotEnoughSigners)?;
+    // once the execution has started, the approval is settled: the remaining instructions have to be executable even if
+    // the executed ones changed the owners or thresholds of the multisig
+    if transaction.executed_up_to > 0 {
+        return Ok(ValidatedAccounts { multisig_account, multisig_signer, transaction_account, refundee, executor, multisig, transaction, vault_bump });
+    }
+    assert_that(multisig.owner_set_seqno == transaction.owner_set_seqno, MultisigError::InvalidOwnerSetSequenceNumber)?;
+    let clock = Clock::get()?;
+    assert_that(!transaction.is_expired(&clock), MultisigError::TransactionExpired)?;
+    assert_that(!transaction.rejected, MultisigError::TransactionRejected)?;
+    assert_that(transaction.approval_weight(&multisig) >= multisig.threshold as u32, MultisigError::NotEnoughSigners)?;
+    if multisig.time_lock_seconds > 0 {
+        // a transaction that meets the threshold without a recorded time (e.g. after the threshold was lowered)
+        // has to be re-approved by an owner to start its time lock
+        let unlocks_at = transaction.threshold_reached_at.saturating_add(multisig.time_lock_seconds as i64);
+        assert_that(
+            transaction.threshold_reached_at > 0 && clock.unix_timestamp >= unlocks_at,
+            MultisigError::TimeLockNotElapsed,
+        )?;
+    }
 
-    Ok(ValidatedAccounts { multisig_account, multisig_signer, transaction_account, refundee, multisig, transaction })
+    Ok(ValidatedAccounts { multisig_account, multisig_signer, transaction_account, refundee, executor, multisig, transaction, vault_bump })
 }
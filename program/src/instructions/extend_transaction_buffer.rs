@@ -0,0 +1,48 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::instructions::common::resize_account;
+use crate::state::transaction_buffer::TransactionBuffer;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::account_info::next_account_info;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+use solana_sdk_ids::system_program;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ExtendTransactionBufferInstruction {
+    // the next chunk of the borsh serialized instructions
+    pub buffer: Vec<u8>,
+}
+
+/// Appends a chunk to a transaction buffer, growing the account (and its rent exemption) accordingly.
+pub fn extend_transaction_buffer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: ExtendTransactionBufferInstruction,
+) -> ProgramResult {
+    msg!("invoke extend_transaction_buffer - {} bytes", instruction.buffer.len());
+
+    let accounts_iter = &mut accounts.iter();
+    let transaction_buffer_account = next_account_info(accounts_iter)?;
+    let creator = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *transaction_buffer_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(system_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
+    let mut transaction_buffer = TransactionBuffer::checked_deserialize(&transaction_buffer_account.data.borrow())?;
+    assert_that(
+        creator.is_signer && transaction_buffer.creator == *creator.key,
+        MultisigError::InvalidTransactionBufferCreator,
+    )?;
+    assert_that(
+        !instruction.buffer.is_empty()
+            && transaction_buffer.buffer.len() + instruction.buffer.len() <= transaction_buffer.final_size as usize,
+        MultisigError::InvalidTransactionBufferSize,
+    )?;
+
+    transaction_buffer.buffer.extend_from_slice(&instruction.buffer);
+    resize_account(transaction_buffer_account, payer, system_program, transaction_buffer.len())?;
+    transaction_buffer.serialize(&mut &mut transaction_buffer_account.data.borrow_mut()[..])?;
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::close_account;
+use crate::instructions::propose_transaction::{create_transaction, ProposeTransactionInstruction, TransactionInstructionData};
+use crate::state::transaction_buffer::TransactionBuffer;
+use borsh::BorshDeserialize;
+use solana_program::account_info::next_account_info;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+
+/// Turns a complete transaction buffer into a proposed transaction (exactly as if its instructions had been passed to
+/// `propose_transaction`) and closes the buffer, refunding its rent to the creator.
+pub fn finalize_transaction_buffer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("invoke finalize_transaction_buffer");
+
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let transaction_account = next_account_info(accounts_iter)?;
+    let transaction_buffer_account = next_account_info(accounts_iter)?;
+    let creator = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    // the address lookup tables of the transaction follow
+
+    assert_that(*program_id == *transaction_buffer_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    let transaction_buffer = TransactionBuffer::checked_deserialize(&transaction_buffer_account.data.borrow())?;
+    assert_that(transaction_buffer.multisig == *multisig_account.key, MultisigError::InvalidTransactionAccount)?;
+    assert_that(
+        creator.is_signer && transaction_buffer.creator == *creator.key,
+        MultisigError::InvalidTransactionBufferCreator,
+    )?;
+    assert_that(creator.is_writable, MultisigError::ImmutableRefundeeAccount)?;
+    assert_that(transaction_buffer.is_complete(), MultisigError::TransactionBufferHashMismatch)?;
+    let instructions = Vec::<TransactionInstructionData>::try_from_slice(&transaction_buffer.buffer)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    create_transaction(
+        program_id,
+        multisig_account,
+        transaction_account,
+        creator,
+        payer,
+        system_program,
+        accounts_iter.as_slice(),
+        ProposeTransactionInstruction {
+            instructions,
+            expiry: transaction_buffer.expiry,
+            vault_index: transaction_buffer.vault_index,
+            address_lookup_tables: transaction_buffer.address_lookup_tables,
+            ephemeral_signers: transaction_buffer.ephemeral_signers,
+            memo: transaction_buffer.memo,
+            description_hash: transaction_buffer.description_hash,
+        },
+        vec![],
+    )?;
+    close_account(transaction_buffer_account, creator)?;
+
+    MultisigEvent::TransactionBufferClosed {
+        multisig: *multisig_account.key,
+        transaction_buffer: *transaction_buffer_account.key,
+        creator: *creator.key,
+    }
+    .emit()
+}
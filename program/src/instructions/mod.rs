@@ -6,5 +6,20 @@ pub mod propose_transaction;
 pub mod approve_transaction;
 pub mod execute_transaction;
 pub mod cancel_transaction;
+pub mod change_time_lock;
+pub mod close_stale_transaction;
+pub mod revoke_approval;
+pub mod reject_transaction;
+pub mod change_cancel_threshold;
+pub mod add_spending_limit;
+pub mod remove_spending_limit;
+pub mod use_spending_limit;
+pub mod create_transaction_buffer;
+pub mod extend_transaction_buffer;
+pub mod finalize_transaction_buffer;
+pub mod close_multisig;
+pub mod approve_with_signatures;
+pub mod propose_config_transaction;
+pub mod close_transaction_buffer;
 
 mod common;
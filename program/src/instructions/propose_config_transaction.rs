@@ -0,0 +1,121 @@
+use crate::errors::{assert_present, assert_that, MultisigError};
+use crate::instructions::common::{owner_capacity, validate_owners, validate_threshold};
+use crate::instructions::propose_transaction::{create_transaction, ProposeTransactionInstruction, TransactionExpiry};
+use crate::state::multisig::{Multisig, Owner};
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankType;
+use solana_program::account_info::next_account_info;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+
+/// A typed change to the configuration of the multisig, applied directly by `execute_transaction` instead of invoking
+/// the multisig program with the multisig signer.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, ShankType)]
+pub enum ConfigAction {
+    AddOwner(Owner),
+    RemoveOwner(Pubkey),
+    ChangeThreshold(u16),
+    ChangeTimeLock(u32),
+}
+impl ConfigAction {
+    pub fn len(&self) -> usize {
+        1 + match self {
+            ConfigAction::AddOwner(_) => Owner::LEN,
+            ConfigAction::RemoveOwner(_) => 32,
+            ConfigAction::ChangeThreshold(_) => 2,
+            ConfigAction::ChangeTimeLock(_) => 4,
+        }
+    }
+}
+
+/// The configuration resulting from applying config actions to a multisig (None where it is left unchanged).
+pub struct ConfigChange {
+    pub owners: Option<Vec<Owner>>,
+    pub threshold: Option<u16>,
+    pub time_lock_seconds: Option<u32>,
+}
+
+/// Applies the given actions in order to the configuration of the multisig and validates the result like `set_owners`,
+/// `change_threshold` and `change_time_lock` would. The owner list cannot grow beyond the capacity of the multisig
+/// account, as executing a config transaction has no payer to resize it.
+pub fn resolve_config_actions(multisig: &Multisig, actions: &[ConfigAction]) -> Result<ConfigChange, ProgramError> {
+    assert_that(!actions.is_empty(), MultisigError::InvalidConfigActions)?;
+    let mut owners: Option<Vec<Owner>> = None;
+    let mut threshold = None;
+    let mut time_lock_seconds = None;
+    for action in actions {
+        match action {
+            ConfigAction::AddOwner(owner) => owners.get_or_insert_with(|| multisig.owners.clone()).push(owner.clone()),
+            ConfigAction::RemoveOwner(pubkey) => {
+                let owners = owners.get_or_insert_with(|| multisig.owners.clone());
+                let index = assert_present(
+                    owners.iter().position(|owner| owner.pubkey == *pubkey),
+                    MultisigError::InvalidConfigActions,
+                )?;
+                owners.remove(index);
+            }
+            ConfigAction::ChangeThreshold(new_threshold) => threshold = Some(*new_threshold),
+            ConfigAction::ChangeTimeLock(new_time_lock_seconds) => time_lock_seconds = Some(*new_time_lock_seconds),
+        }
+    }
+    if let Some(owners) = &owners {
+        validate_owners(owners)?;
+        assert_that(Owner::LEN * owners.len() <= owner_capacity(multisig), MultisigError::TooManyOwners)?;
+    }
+    if let Some(threshold) = threshold {
+        validate_threshold(threshold, owners.as_deref().unwrap_or(&multisig.owners))?;
+    }
+    Ok(ConfigChange { owners, threshold, time_lock_seconds })
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ProposeConfigTransactionInstruction {
+    pub actions: Vec<ConfigAction>,
+    pub expiry: Option<TransactionExpiry>,
+    // a short note on the purpose of the transaction (at most `Transaction::MAX_MEMO_LEN` bytes)
+    pub memo: Option<String>,
+    // the hash of an off-chain document describing the transaction
+    pub description_hash: Option<[u8; 32]>,
+}
+
+/// Proposes a config transaction, i.e. a transaction without instructions whose actions are applied to the multisig
+/// when it is executed. The actions are validated against the current configuration upfront, so that owners never
+/// vote on a change that could not be applied.
+pub fn propose_config_transaction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: ProposeConfigTransactionInstruction,
+) -> ProgramResult {
+    msg!("invoke propose_config_transaction - {:?}", instruction);
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let transaction_account = next_account_info(accounts_iter)?;
+    let proposer = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    resolve_config_actions(&multisig, &instruction.actions)?;
+
+    create_transaction(
+        program_id,
+        multisig_account,
+        transaction_account,
+        proposer,
+        payer,
+        system_program,
+        &[],
+        ProposeTransactionInstruction {
+            instructions: vec![],
+            expiry: instruction.expiry,
+            vault_index: 0,
+            address_lookup_tables: vec![],
+            ephemeral_signers: 0,
+            memo: instruction.memo,
+            description_hash: instruction.description_hash,
+        },
+        instruction.actions,
+    )
+}
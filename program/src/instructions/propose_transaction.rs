@@ -1,18 +1,20 @@
-use crate::errors::{assert_present, assert_that, MultisigError};
-use crate::state::multisig::Multisig;
+use crate::errors::{assert_success, assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::propose_config_transaction::ConfigAction;
+use crate::instructions::common::{create_pda_account, lookup_table_address, record_threshold_reached, validate_owner_permission};
+use crate::state::multisig::{Multisig, PERMISSION_PROPOSE, PERMISSION_VOTE};
 use crate::state::transaction::Transaction;
+use crate::state::transaction_receipt::ExecutionReceipt;
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankType;
 use solana_program::account_info::next_account_info;
+use solana_program::clock::Clock;
 use solana_program::instruction::{AccountMeta, Instruction};
-use solana_program::program::invoke;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
-use solana_program::rent::Rent;
 use solana_program::sysvar::Sysvar;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
 use solana_sdk_ids::system_program;
-use solana_system_interface::instruction as system_instruction;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, ShankType)]
 pub struct TransactionInstructionAccount {
@@ -29,25 +31,88 @@ impl From<&TransactionInstructionAccount> for AccountMeta {
     }
 }
 
+/// An account of an instruction that is only resolved from one of the address lookup tables of the transaction when it
+/// is executed, so that it takes 4 instead of 34 bytes in the transaction account. Lookup accounts cannot be signers.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, ShankType)]
+pub struct TransactionLookupAccount {
+    // the position of the account among all accounts of the instruction
+    pub position: u8,
+    // index into the `address_lookup_tables` of the transaction
+    pub table_index: u8,
+    // index of the address within the lookup table
+    pub address_index: u8,
+    pub is_writable: bool,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, ShankType)]
 pub struct TransactionInstructionData {
     pub program_id: Pubkey,
     pub accounts: Vec<TransactionInstructionAccount>,
+    // accounts inserted into `accounts` at their position (in ascending order of position)
+    pub lookup_accounts: Vec<TransactionLookupAccount>,
     pub data: Vec<u8>,
 }
 impl TransactionInstructionData {
     pub fn len(&self) -> usize {
         32 +                                          // program_id
             4 + (32 + 1 + 1) * self.accounts.len() +  // accounts
+            4 + 4 * self.lookup_accounts.len() +      // lookup_accounts
             4 + self.data.len()                       // data
     }
+
+    /// Builds the instruction to invoke, resolving the lookup accounts from the given address lookup tables (in the
+    /// order of the `address_lookup_tables` of the transaction).
+    pub fn to_instruction(&self, address_lookup_tables: &[&AccountInfo]) -> Result<Instruction, ProgramError> {
+        let mut accounts: Vec<AccountMeta> = self.accounts.iter().map(Into::into).collect();
+        for lookup in &self.lookup_accounts {
+            let table = address_lookup_tables
+                .get(lookup.table_index as usize)
+                .ok_or(MultisigError::InvalidAddressLookupTable)?;
+            let pubkey = lookup_table_address(table, lookup.address_index)?;
+            assert_that(lookup.position as usize <= accounts.len(), MultisigError::InvalidAddressLookupTable)?;
+            accounts.insert(lookup.position as usize, match lookup.is_writable {
+                false => AccountMeta::new_readonly(pubkey, false),
+                true => AccountMeta::new(pubkey, false),
+            });
+        }
+        Ok(Instruction { program_id: self.program_id, accounts, data: self.data.clone() })
+    }
+
+    /// Checks that every lookup account refers to an address that already exists in the given address lookup tables.
+    /// As lookup tables are append-only, the addresses approved by the owners are the ones resolved on execution.
+    fn validate_lookups(&self, address_lookup_tables: &[&AccountInfo]) -> ProgramResult {
+        let account_count = self.accounts.len() + self.lookup_accounts.len();
+        assert_that(
+            self.lookup_accounts.windows(2).all(|pair| pair[0].position < pair[1].position),
+            MultisigError::InvalidAddressLookupTable,
+        )?;
+        for lookup in &self.lookup_accounts {
+            assert_that((lookup.position as usize) < account_count, MultisigError::InvalidAddressLookupTable)?;
+            let table = address_lookup_tables
+                .get(lookup.table_index as usize)
+                .ok_or(MultisigError::InvalidAddressLookupTable)?;
+            lookup_table_address(table, lookup.address_index)?;
+        }
+        Ok(())
+    }
+}
+
+/// Deadline after which a proposed transaction can no longer be approved or executed (the given slot or unix timestamp
+/// itself is still valid).
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, ShankType)]
+pub enum TransactionExpiry {
+    Slot(u64),
+    UnixTimestamp(i64),
 }
-impl From<&TransactionInstructionData> for Instruction {
-    fn from(ix: &TransactionInstructionData) -> Instruction {
-        Instruction {
-            program_id: ix.program_id,
-            accounts: ix.accounts.iter().map(Into::into).collect(),
-            data: ix.data.clone(),
+impl TransactionExpiry {
+    pub fn len(&self) -> usize {
+        1 + 8  // variant + slot/unix timestamp
+    }
+
+    pub fn has_passed(&self, clock: &Clock) -> bool {
+        match self {
+            TransactionExpiry::Slot(slot) => clock.slot > *slot,
+            TransactionExpiry::UnixTimestamp(unix_timestamp) => clock.unix_timestamp > *unix_timestamp,
         }
     }
 }
@@ -55,6 +120,16 @@ impl From<&TransactionInstructionData> for Instruction {
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct ProposeTransactionInstruction {
     pub instructions: Vec<TransactionInstructionData>,
+    pub expiry: Option<TransactionExpiry>,
+    pub vault_index: u8,
+    // the address lookup tables the lookup accounts of the instructions refer to
+    pub address_lookup_tables: Vec<Pubkey>,
+    // the number of ephemeral signer PDAs the instructions use
+    pub ephemeral_signers: u8,
+    // a short note on the purpose of the transaction (at most `Transaction::MAX_MEMO_LEN` bytes)
+    pub memo: Option<String>,
+    // the hash of an off-chain document describing the transaction
+    pub description_hash: Option<[u8; 32]>,
 }
 
 pub fn propose_transaction(
@@ -69,37 +144,108 @@ pub fn propose_transaction(
     let proposer = next_account_info(accounts_iter)?;
     let payer = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
+    // the address lookup tables of the transaction follow
 
-    validate(program_id, multisig_account, proposer, system_program, &instruction)?;
+    create_transaction(
+        program_id,
+        multisig_account,
+        transaction_account,
+        proposer,
+        payer,
+        system_program,
+        accounts_iter.as_slice(),
+        instruction,
+        vec![],
+    )
+}
+
+/// Creates the transaction account for the next transaction index of the multisig. Shared by `propose_transaction`,
+/// `finalize_transaction_buffer` and `propose_config_transaction` (the only one passing config actions).
+#[allow(clippy::too_many_arguments)]
+pub fn create_transaction<'a>(
+    program_id: &Pubkey,
+    multisig_account: &AccountInfo<'a>,
+    transaction_account: &AccountInfo<'a>,
+    proposer: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    address_lookup_tables: &[AccountInfo<'a>],
+    instruction: ProposeTransactionInstruction,
+    config_actions: Vec<ConfigAction>,
+) -> ProgramResult {
+    validate(program_id, multisig_account, proposer, system_program, address_lookup_tables, &instruction, &config_actions)?;
+
+    let mut multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    let owner_index = validate_owner_permission(&multisig, proposer.key, PERMISSION_PROPOSE)?;
+    let transaction_index = multisig.transaction_index.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    let (transaction_address, bump) = Transaction::find_address(multisig_account.key, transaction_index, program_id);
+    assert_that(*transaction_account.key == transaction_address, ProgramError::InvalidSeeds)?;
 
-    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
-    let owner_index = assert_present(
-        multisig.owners.iter().position(|a| a == proposer.key),
-        MultisigError::InvalidOwner,
-    )?;
     let mut signers = Vec::new();
     signers.resize(multisig.owners.len(), false);
-    signers[owner_index] = true;
+    // proposing only counts as an approval for owners that may also vote
+    signers[owner_index] = multisig.owners[owner_index].has_permission(PERMISSION_VOTE);
 
-    let transaction_data: Transaction = Transaction {
+    let clock = Clock::get()?;
+    let receipts = (0..instruction.instructions.len())
+        .map(|index| {
+            let instruction_index = assert_success(u16::try_from(index), MultisigError::TooManyInstructions)?;
+            Ok(ExecutionReceipt { instruction_index, ..Default::default() })
+        })
+        .collect::<Result<_, ProgramError>>()?;
+    let mut transaction_data: Transaction = Transaction {
+        discriminator: Transaction::DISCRIMINATOR,
+        version: Transaction::VERSION,
         multisig: *multisig_account.key,
+        transaction_index,
         instructions: instruction.instructions,
+        config_actions,
+        address_lookup_tables: instruction.address_lookup_tables,
+        vault_index: instruction.vault_index,
+        ephemeral_signers: instruction.ephemeral_signers,
+        executed_up_to: 0,
+        receipts,
         signers,
+        rejectors: vec![false; multisig.owners.len()],
+        revokers: vec![false; multisig.owners.len()],
+        rejected: false,
+        cancellers: vec![false; multisig.owners.len()],
+        cancellers_owner_set_seqno: multisig.owner_set_seqno,
         owner_set_seqno: multisig.owner_set_seqno,
+        threshold_reached_at: 0,
+        proposer: *proposer.key,
+        rent_payer: *payer.key,
+        created_at_slot: clock.slot,
+        created_at: clock.unix_timestamp,
+        memo: instruction.memo,
+        description_hash: instruction.description_hash,
+        expiry: instruction.expiry,
     };
-    invoke(
-        &system_instruction::create_account(
-            payer.key,
-            transaction_account.key,
-            Rent::get()?.minimum_balance(transaction_data.len()),
-            transaction_data.len().try_into().unwrap(),
-            program_id,
-        ),
-        &[payer.clone(), transaction_account.clone(), system_program.clone()],
+    record_threshold_reached(&multisig, &mut transaction_data)?;
+    create_pda_account(
+        payer,
+        transaction_account,
+        system_program,
+        transaction_data.len(),
+        program_id,
+        &[multisig_account.key.as_ref(), Transaction::SEED_PREFIX, &transaction_index.to_le_bytes(), &[bump]],
     )?;
     transaction_data.serialize(&mut &mut transaction_account.data.borrow_mut()[..])?;
 
-    Ok(())
+    multisig.transaction_index = transaction_index;
+    multisig.pending_transactions = multisig.pending_transactions.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    multisig.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+
+    MultisigEvent::TransactionProposed {
+        multisig: *multisig_account.key,
+        transaction: *transaction_account.key,
+        proposer: *proposer.key,
+        transaction_index,
+        vault_index: transaction_data.vault_index,
+        approval_weight: transaction_data.approval_weight(&multisig),
+        threshold_reached_at: transaction_data.threshold_reached_at,
+    }
+    .emit()
 }
 
 fn validate(
@@ -107,7 +253,9 @@ fn validate(
     multisig_account: &AccountInfo,
     proposer: &AccountInfo,
     system_program: &AccountInfo,
+    address_lookup_tables: &[AccountInfo],
     instruction: &ProposeTransactionInstruction,
+    config_actions: &[ConfigAction],
 ) -> ProgramResult {
     assert_that(
         *program_id == *multisig_account.owner,
@@ -117,7 +265,30 @@ fn validate(
         system_program.key == &system_program::id(),
         ProgramError::IncorrectProgramId,
     )?;
+    assert_that(multisig_account.is_writable, MultisigError::ImmutableMultisigAccount)?;
     assert_that(proposer.is_signer, MultisigError::ProposerNotSigner)?;
-    assert_that(!instruction.instructions.is_empty(), MultisigError::MissingInstructions)?;
+    assert_that(
+        !instruction.instructions.is_empty() || !config_actions.is_empty(),
+        MultisigError::MissingInstructions,
+    )?;
+    // instructions are indexed by u16 (see `Transaction::executed_up_to` and `ExecutionReceipt::instruction_index`)
+    assert_that(instruction.instructions.len() <= u16::MAX as usize, MultisigError::TooManyInstructions)?;
+    let address_lookup_tables = instruction
+        .address_lookup_tables
+        .iter()
+        .map(|table| {
+            address_lookup_tables.iter().find(|account| account.key == table).ok_or(MultisigError::InvalidAddressLookupTable)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for ix in &instruction.instructions {
+        ix.validate_lookups(&address_lookup_tables)?;
+    }
+    assert_that(
+        instruction.memo.as_ref().is_none_or(|memo| memo.len() <= Transaction::MAX_MEMO_LEN),
+        MultisigError::MemoTooLong,
+    )?;
+    if let Some(expiry) = &instruction.expiry {
+        assert_that(!expiry.has_passed(&Clock::get()?), MultisigError::TransactionExpired)?;
+    }
     Ok(())
 }
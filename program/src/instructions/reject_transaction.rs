@@ -0,0 +1,61 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::validate_owner_permission;
+use crate::state::multisig::{Multisig, PERMISSION_VOTE};
+use crate::state::transaction::Transaction;
+use borsh::BorshSerialize;
+use solana_program::account_info::next_account_info;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+
+pub fn reject_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("invoke reject_transaction");
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let transaction_account = next_account_info(accounts_iter)?;
+    let rejector = next_account_info(accounts_iter)?;
+
+    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    let mut transaction = Transaction::checked_deserialize(&transaction_account.data.borrow())?;
+
+    validate(program_id, &multisig, &transaction, rejector, transaction_account, multisig_account)?;
+
+    let owner_index = validate_owner_permission(&multisig, rejector.key, PERMISSION_VOTE)?;
+    transaction.rejectors[owner_index] = true;
+    transaction.signers[owner_index] = false;
+    if transaction.approval_weight(&multisig) < multisig.threshold as u32 {
+        transaction.threshold_reached_at = 0;
+    }
+    // once the weight of the remaining voting owners cannot reach the threshold anymore, anyone may close the transaction
+    if multisig.total_weight() - transaction.rejection_weight(&multisig) < multisig.threshold as u32 {
+        transaction.rejected = true;
+    }
+
+    transaction.serialize(&mut &mut transaction_account.data.borrow_mut()[..])?;
+
+    MultisigEvent::TransactionRejected {
+        multisig: *multisig_account.key,
+        transaction: *transaction_account.key,
+        rejector: *rejector.key,
+        rejection_weight: transaction.rejection_weight(&multisig),
+        rejected: transaction.rejected,
+    }
+    .emit()
+}
+
+fn validate(
+    program_id: &Pubkey,
+    multisig: &Multisig,
+    transaction: &Transaction,
+    rejector: &AccountInfo,
+    transaction_account: &AccountInfo,
+    multisig_account: &AccountInfo,
+) -> ProgramResult {
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(*program_id == *transaction_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(multisig.owner_set_seqno == transaction.owner_set_seqno, MultisigError::InvalidOwnerSetSequenceNumber)?;
+    assert_that(rejector.is_signer, MultisigError::OwnerNotSigner)?;
+    assert_that(transaction_account.is_writable, MultisigError::ImmutableTransactionAccount)?;
+    assert_that(transaction.multisig == *multisig_account.key, MultisigError::InvalidTransactionAccount)?;
+    Ok(())
+}
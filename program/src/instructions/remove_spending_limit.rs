@@ -0,0 +1,40 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{close_account, validate_signer};
+use crate::state::multisig::Multisig;
+use crate::state::spending_limit::SpendingLimit;
+use borsh::BorshSerialize;
+use solana_program::account_info::next_account_info;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+
+pub fn remove_spending_limit(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("invoke remove_spending_limit");
+
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let multisig_signer = next_account_info(accounts_iter)?;
+    let spending_limit_account = next_account_info(accounts_iter)?;
+    let refundee = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(*program_id == *spending_limit_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(multisig_account.is_writable, MultisigError::ImmutableMultisigAccount)?;
+    let mut multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    let spending_limit = SpendingLimit::checked_deserialize(&spending_limit_account.data.borrow())?;
+    validate_signer(multisig_signer, multisig_account, &multisig, program_id)?;
+    assert_that(spending_limit.multisig == *multisig_account.key, MultisigError::InvalidSpendingLimitAccount)?;
+    assert_that(refundee.key != spending_limit_account.key, MultisigError::InvalidRefundeeAccount)?;
+    assert_that(refundee.is_writable, MultisigError::ImmutableRefundeeAccount)?;
+
+    multisig.spending_limits = multisig.spending_limits.checked_sub(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    multisig.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+    close_account(spending_limit_account, refundee)?;
+
+    MultisigEvent::SpendingLimitRemoved {
+        multisig: *multisig_account.key,
+        spending_limit: *spending_limit_account.key,
+    }
+    .emit()
+}
@@ -1,5 +1,5 @@
-use crate::instructions::common::{execute_set_owners, validate_owners, validate_signer};
-use crate::state::multisig::Multisig;
+use crate::instructions::common::{ensure_owner_capacity, execute_set_owners, validate_owners, validate_signer};
+use crate::state::multisig::{Multisig, Owner};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::account_info::next_account_info;
 use solana_program::pubkey::Pubkey;
@@ -10,7 +10,7 @@ use crate::errors::{assert_that, MultisigError};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct SetOwnersInstruction {
-    pub owners: Vec<Pubkey>,
+    pub owners: Vec<Owner>,
 }
 
 pub fn set_owners(
@@ -23,13 +23,17 @@ pub fn set_owners(
     let accounts_iter = &mut accounts.iter();
     let multisig_account = next_account_info(accounts_iter)?;
     let multisig_signer = next_account_info(accounts_iter)?;
+    // only required to grow the owner list beyond its original size
+    let payer = next_account_info(accounts_iter).ok();
+    let system_program = next_account_info(accounts_iter).ok();
     let mut multisig_data = Multisig::checked_deserialize(&multisig_account.data.borrow_mut())?;
 
     // Defense in depth: validate_signer below also implicitly requires this (only the multisig program can produce
     // the PDA signer), but we assert it up front for symmetry with propose/approve/execute/cancel.
     assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
     validate_signer(multisig_signer, multisig_account, &multisig_data, program_id)?;
-    validate_owners(&multisig_data, &instruction.owners)?;
+    validate_owners(&instruction.owners)?;
 
+    ensure_owner_capacity(multisig_account, &multisig_data, instruction.owners.len(), payer, system_program)?;
     execute_set_owners(&multisig_account, &mut multisig_data, instruction.owners)
 }
@@ -0,0 +1,112 @@
+use crate::errors::{assert_that, MultisigError};
+use crate::events::MultisigEvent;
+use crate::instructions::common::{invoke_signed_by_vault, validate_vault};
+use crate::state::multisig::Multisig;
+use crate::state::spending_limit::SpendingLimit;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::account_info::next_account_info;
+use solana_program::clock::Clock;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey};
+use solana_sdk_ids::system_program;
+use solana_system_interface::instruction as system_instruction;
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+// index of the `TransferChecked` instruction in both token programs
+const TRANSFER_CHECKED: u8 = 12;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct UseSpendingLimitInstruction {
+    pub amount: u64,
+    // the decimals of the mint (ignored for lamports)
+    pub decimals: u8,
+}
+
+/// Transfers lamports or tokens out of a vault within the limits of a spending limit, without a proposal.
+pub fn use_spending_limit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: UseSpendingLimitInstruction,
+) -> ProgramResult {
+    msg!("invoke use_spending_limit - {:?}", instruction);
+
+    let accounts_iter = &mut accounts.iter();
+    let multisig_account = next_account_info(accounts_iter)?;
+    let spending_limit_account = next_account_info(accounts_iter)?;
+    let member = next_account_info(accounts_iter)?;
+    let vault = next_account_info(accounts_iter)?;
+    let destination = next_account_info(accounts_iter)?;
+    let transfer_program = next_account_info(accounts_iter)?;
+
+    assert_that(*program_id == *multisig_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    assert_that(*program_id == *spending_limit_account.owner, MultisigError::AccountOwnedByWrongProgram)?;
+    let multisig = Multisig::checked_deserialize(&multisig_account.data.borrow())?;
+    let mut spending_limit = SpendingLimit::checked_deserialize(&spending_limit_account.data.borrow())?;
+
+    assert_that(spending_limit.multisig == *multisig_account.key, MultisigError::InvalidSpendingLimitAccount)?;
+    assert_that(
+        member.is_signer && spending_limit.members.contains(member.key) && multisig.owner_index(member.key).is_some(),
+        MultisigError::InvalidSpendingLimitMember,
+    )?;
+    let vault_bump = validate_vault(vault, multisig_account, &multisig, spending_limit.vault_index, program_id)?;
+
+    spending_limit.refresh(Clock::get()?.unix_timestamp);
+    assert_that(instruction.amount <= spending_limit.remaining_amount, MultisigError::SpendingLimitExceeded)?;
+    spending_limit.remaining_amount -= instruction.amount;
+    spending_limit.serialize(&mut &mut spending_limit_account.data.borrow_mut()[..])?;
+
+    if spending_limit.is_sol() {
+        assert_that(transfer_program.key == &system_program::ID, ProgramError::IncorrectProgramId)?;
+        invoke_signed_by_vault(
+            &system_instruction::transfer(vault.key, destination.key, instruction.amount),
+            &[vault.clone(), destination.clone(), transfer_program.clone()],
+            multisig_account.key,
+            spending_limit.vault_index,
+            vault_bump,
+            &[],
+        )?;
+    } else {
+        let mint = next_account_info(accounts_iter)?;
+        let source = next_account_info(accounts_iter)?;
+        assert_that(
+            *transfer_program.key == TOKEN_PROGRAM_ID || *transfer_program.key == TOKEN_2022_PROGRAM_ID,
+            ProgramError::IncorrectProgramId,
+        )?;
+        assert_that(spending_limit.mint == *mint.key, MultisigError::InvalidSpendingLimitAccount)?;
+        let mut data = vec![TRANSFER_CHECKED];
+        data.extend_from_slice(&instruction.amount.to_le_bytes());
+        data.push(instruction.decimals);
+        let transfer_checked = Instruction {
+            program_id: *transfer_program.key,
+            accounts: vec![
+                AccountMeta::new(*source.key, false),
+                AccountMeta::new_readonly(*mint.key, false),
+                AccountMeta::new(*destination.key, false),
+                AccountMeta::new_readonly(*vault.key, true),
+            ],
+            data,
+        };
+        invoke_signed_by_vault(
+            &transfer_checked,
+            &[source.clone(), mint.clone(), destination.clone(), vault.clone(), transfer_program.clone()],
+            multisig_account.key,
+            spending_limit.vault_index,
+            vault_bump,
+            &[],
+        )?;
+    }
+
+    MultisigEvent::SpendingLimitUsed {
+        multisig: *multisig_account.key,
+        spending_limit: *spending_limit_account.key,
+        member: *member.key,
+        destination: *destination.key,
+        amount: instruction.amount,
+        remaining_amount: spending_limit.remaining_amount,
+    }
+    .emit()
+}
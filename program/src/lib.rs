@@ -1,4 +1,5 @@
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod processor;
 pub mod state;
@@ -5,16 +5,31 @@ use solana_program::entrypoint::ProgramResult;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 
-use crate::instructions::approve_transaction::approve_transaction;
+use crate::instructions::approve_transaction::{approve_transaction, ApproveTransactionInstruction};
 use crate::instructions::cancel_transaction::cancel_transaction;
 use crate::instructions::create_multisig::{create_multisig, CreateMultisigInstruction};
-use crate::instructions::execute_transaction::execute_transaction;
+use crate::instructions::execute_transaction::{execute_transaction, ExecuteTransactionInstruction};
 use crate::instructions::propose_transaction::{
     propose_transaction, ProposeTransactionInstruction,
 };
 use crate::instructions::set_owners::{set_owners, SetOwnersInstruction};
 use crate::instructions::change_threshold::{change_threshold, ChangeThresholdInstruction};
 use crate::instructions::set_owners_and_change_threshold::{set_owners_and_change_threshold, SetOwnersAndChangeThresholdInstruction};
+use crate::instructions::change_time_lock::{change_time_lock, ChangeTimeLockInstruction};
+use crate::instructions::close_stale_transaction::close_stale_transaction;
+use crate::instructions::revoke_approval::revoke_approval;
+use crate::instructions::reject_transaction::reject_transaction;
+use crate::instructions::change_cancel_threshold::{change_cancel_threshold, ChangeCancelThresholdInstruction};
+use crate::instructions::add_spending_limit::{add_spending_limit, AddSpendingLimitInstruction};
+use crate::instructions::remove_spending_limit::remove_spending_limit;
+use crate::instructions::use_spending_limit::{use_spending_limit, UseSpendingLimitInstruction};
+use crate::instructions::create_transaction_buffer::{create_transaction_buffer, CreateTransactionBufferInstruction};
+use crate::instructions::extend_transaction_buffer::{extend_transaction_buffer, ExtendTransactionBufferInstruction};
+use crate::instructions::finalize_transaction_buffer::finalize_transaction_buffer;
+use crate::instructions::close_multisig::close_multisig;
+use crate::instructions::approve_with_signatures::approve_with_signatures;
+use crate::instructions::propose_config_transaction::{propose_config_transaction, ProposeConfigTransactionInstruction};
+use crate::instructions::close_transaction_buffer::close_transaction_buffer;
 
 #[derive(BorshSerialize, BorshDeserialize, ShankInstruction)]
 pub enum MultisigInstruction {
@@ -26,6 +41,8 @@ pub enum MultisigInstruction {
 
     #[account(0, writable, name = "multisig", desc = "The multisig account")]
     #[account(1, signer, name = "multisig_signer", desc = "The multisig PDA signer")]
+    #[account(2, optional, writable, signer, name = "payer", desc = "Pays for growing the multisig account beyond its original size (e.g. the multisig signer)")]
+    #[account(3, optional, name = "system_program", desc = "The system program (required with the payer)")]
     SetOwners(SetOwnersInstruction),
 
     #[account(0, writable, name = "multisig", desc = "The multisig account")]
@@ -34,32 +51,127 @@ pub enum MultisigInstruction {
 
     #[account(0, writable, name = "multisig", desc = "The multisig account")]
     #[account(1, signer, name = "multisig_signer", desc = "The multisig PDA signer")]
+    #[account(2, optional, writable, signer, name = "payer", desc = "Pays for growing the multisig account beyond its original size (e.g. the multisig signer)")]
+    #[account(3, optional, name = "system_program", desc = "The system program (required with the payer)")]
     SetOwnersAndChangeThreshold(SetOwnersAndChangeThresholdInstruction),
 
-    #[account(0, name = "multisig", desc = "The multisig account")]
-    #[account(1, writable, signer, name = "transaction", desc = "The transaction account to create")]
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its transaction index and pending transactions are incremented)")]
+    #[account(1, writable, name = "transaction", desc = "The transaction account to create (PDA of [multisig, \"transaction\", index])")]
     #[account(2, signer, name = "proposer", desc = "The proposer (must be an owner)")]
     #[account(3, writable, signer, name = "payer", desc = "The payer for account creation")]
     #[account(4, name = "system_program", desc = "The system program")]
+    // followed by the address lookup tables of the transaction
     ProposeTransaction(ProposeTransactionInstruction),
 
     #[account(0, name = "multisig", desc = "The multisig account")]
     #[account(1, writable, name = "transaction", desc = "The transaction account")]
     #[account(2, signer, name = "approver", desc = "The approver (must be an owner)")]
-    ApproveTransaction(),
+    ApproveTransaction(ApproveTransactionInstruction),
 
-    #[account(0, name = "multisig", desc = "The multisig account")]
-    #[account(1, name = "multisig_signer", desc = "The multisig PDA signer")]
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its pending transactions are decremented)")]
+    #[account(1, name = "multisig_signer", desc = "The signer PDA of the vault the transaction was proposed for")]
     #[account(2, writable, name = "transaction", desc = "The transaction account")]
-    #[account(3, writable, name = "refundee", desc = "The account to receive the rent refund")]
+    #[account(3, writable, name = "refundee", desc = "The rent payer of the transaction (receives the rent refund)")]
     #[account(4, signer, name = "executor", desc = "The executor (must be an owner)")]
-    ExecuteTransaction(),
+    ExecuteTransaction(ExecuteTransactionInstruction),
 
-    #[account(0, name = "multisig", desc = "The multisig account")]
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its pending transactions are decremented)")]
     #[account(1, writable, name = "transaction", desc = "The transaction account")]
-    #[account(2, writable, name = "refundee", desc = "The account to receive the rent refund")]
+    #[account(2, writable, name = "refundee", desc = "The rent payer of the transaction (receives the rent refund)")]
     #[account(3, signer, name = "executor", desc = "The executor (must be an owner)")]
+    #[account(4, optional, writable, signer, name = "payer", desc = "Pays for growing the transaction account to record the cancel votes of a larger owner set")]
+    #[account(5, optional, name = "system_program", desc = "The system program (required with the payer)")]
     CancelTransaction(),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account")]
+    #[account(1, signer, name = "multisig_signer", desc = "The multisig PDA signer")]
+    ChangeTimeLock(ChangeTimeLockInstruction),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its pending transactions are decremented)")]
+    #[account(1, writable, name = "transaction", desc = "The stale transaction account")]
+    #[account(2, writable, name = "rent_payer", desc = "The rent payer of the transaction (receives the rent refund)")]
+    CloseStaleTransaction(),
+
+    #[account(0, name = "multisig", desc = "The multisig account")]
+    #[account(1, writable, name = "transaction", desc = "The transaction account")]
+    #[account(2, signer, name = "owner", desc = "The owner revoking their approval")]
+    RevokeApproval(),
+
+    #[account(0, name = "multisig", desc = "The multisig account")]
+    #[account(1, writable, name = "transaction", desc = "The transaction account")]
+    #[account(2, signer, name = "rejector", desc = "The rejecting owner")]
+    RejectTransaction(),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account")]
+    #[account(1, signer, name = "multisig_signer", desc = "The multisig PDA signer")]
+    ChangeCancelThreshold(ChangeCancelThresholdInstruction),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its spending limits are incremented)")]
+    #[account(1, signer, name = "multisig_signer", desc = "The multisig PDA signer")]
+    #[account(2, writable, name = "spending_limit", desc = "The spending limit account to create (PDA of [multisig, \"spending_limit\", vault_index, mint])")]
+    #[account(3, writable, signer, name = "payer", desc = "The payer for account creation")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    AddSpendingLimit(AddSpendingLimitInstruction),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its spending limits are decremented)")]
+    #[account(1, signer, name = "multisig_signer", desc = "The multisig PDA signer")]
+    #[account(2, writable, name = "spending_limit", desc = "The spending limit account to close")]
+    #[account(3, writable, name = "refundee", desc = "The account to receive the rent refund")]
+    RemoveSpendingLimit(),
+
+    #[account(0, name = "multisig", desc = "The multisig account")]
+    #[account(1, writable, name = "spending_limit", desc = "The spending limit account")]
+    #[account(2, signer, name = "member", desc = "A member of the spending limit")]
+    #[account(3, writable, name = "vault", desc = "The signer PDA of the vault of the spending limit")]
+    #[account(4, writable, name = "destination", desc = "The recipient (a token account for SPL mints)")]
+    #[account(5, name = "transfer_program", desc = "The system program, or the token program of the mint")]
+    #[account(6, optional, name = "mint", desc = "The mint of the spending limit (SPL mints only)")]
+    #[account(7, optional, writable, name = "source", desc = "The token account of the vault (SPL mints only)")]
+    UseSpendingLimit(UseSpendingLimitInstruction),
+
+    #[account(0, name = "multisig", desc = "The multisig account")]
+    #[account(1, writable, name = "transaction_buffer", desc = "The transaction buffer account to create (PDA of [multisig, \"transaction_buffer\", creator, buffer_index])")]
+    #[account(2, signer, name = "creator", desc = "The creator of the buffer (must be an owner with propose permission)")]
+    #[account(3, writable, signer, name = "payer", desc = "The payer for account creation")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    CreateTransactionBuffer(CreateTransactionBufferInstruction),
+
+    #[account(0, writable, name = "transaction_buffer", desc = "The transaction buffer account")]
+    #[account(1, signer, name = "creator", desc = "The creator of the buffer")]
+    #[account(2, writable, signer, name = "payer", desc = "The payer for the additional rent")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    ExtendTransactionBuffer(ExtendTransactionBufferInstruction),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its transaction index is incremented)")]
+    #[account(1, writable, name = "transaction", desc = "The transaction account to create (PDA of [multisig, \"transaction\", index])")]
+    #[account(2, writable, name = "transaction_buffer", desc = "The complete transaction buffer account (closed)")]
+    #[account(3, writable, signer, name = "creator", desc = "The creator of the buffer (becomes the proposer and receives the buffer rent refund)")]
+    #[account(4, writable, signer, name = "payer", desc = "The payer for account creation")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    // followed by the address lookup tables of the transaction
+    FinalizeTransactionBuffer(),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account to close")]
+    #[account(1, writable, signer, name = "multisig_signer", desc = "The multisig PDA signer (its remaining lamports are transferred)")]
+    #[account(2, writable, name = "recipient", desc = "The account to receive the remaining lamports")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    CloseMultisig(),
+
+    #[account(0, name = "multisig", desc = "The multisig account")]
+    #[account(1, writable, name = "transaction", desc = "The transaction account")]
+    #[account(2, name = "instructions_sysvar", desc = "The instructions sysvar (to read the ed25519 program instructions)")]
+    ApproveWithSignatures(),
+
+    #[account(0, writable, name = "multisig", desc = "The multisig account (its transaction index and pending transactions are incremented)")]
+    #[account(1, writable, name = "transaction", desc = "The transaction account to create (PDA of [multisig, \"transaction\", index])")]
+    #[account(2, signer, name = "proposer", desc = "The proposer (must be an owner)")]
+    #[account(3, writable, signer, name = "payer", desc = "The payer for account creation")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    ProposeConfigTransaction(ProposeConfigTransactionInstruction),
+
+    #[account(0, writable, name = "transaction_buffer", desc = "The transaction buffer account to close")]
+    #[account(1, writable, signer, name = "creator", desc = "The creator of the buffer (receives the rent refund)")]
+    CloseTransactionBuffer(),
 }
 
 pub fn process_instruction(
@@ -84,9 +196,42 @@ pub fn process_instruction(
             MultisigInstruction::ProposeTransaction(propose_data) => {
                 propose_transaction(program_id, accounts, propose_data)
             }
-            MultisigInstruction::ApproveTransaction() => approve_transaction(program_id, accounts),
-            MultisigInstruction::ExecuteTransaction() => execute_transaction(program_id, accounts),
+            MultisigInstruction::ApproveTransaction(approve_transaction_data) => {
+                approve_transaction(program_id, accounts, approve_transaction_data)
+            }
+            MultisigInstruction::ExecuteTransaction(execute_transaction_data) => {
+                execute_transaction(program_id, accounts, execute_transaction_data)
+            }
             MultisigInstruction::CancelTransaction() => cancel_transaction(program_id, accounts),
+            MultisigInstruction::ChangeTimeLock(change_time_lock_data) => {
+                change_time_lock(program_id, accounts, change_time_lock_data)
+            }
+            MultisigInstruction::CloseStaleTransaction() => close_stale_transaction(program_id, accounts),
+            MultisigInstruction::RevokeApproval() => revoke_approval(program_id, accounts),
+            MultisigInstruction::RejectTransaction() => reject_transaction(program_id, accounts),
+            MultisigInstruction::ChangeCancelThreshold(change_cancel_threshold_data) => {
+                change_cancel_threshold(program_id, accounts, change_cancel_threshold_data)
+            }
+            MultisigInstruction::AddSpendingLimit(add_spending_limit_data) => {
+                add_spending_limit(program_id, accounts, add_spending_limit_data)
+            }
+            MultisigInstruction::RemoveSpendingLimit() => remove_spending_limit(program_id, accounts),
+            MultisigInstruction::UseSpendingLimit(use_spending_limit_data) => {
+                use_spending_limit(program_id, accounts, use_spending_limit_data)
+            }
+            MultisigInstruction::CreateTransactionBuffer(create_transaction_buffer_data) => {
+                create_transaction_buffer(program_id, accounts, create_transaction_buffer_data)
+            }
+            MultisigInstruction::ExtendTransactionBuffer(extend_transaction_buffer_data) => {
+                extend_transaction_buffer(program_id, accounts, extend_transaction_buffer_data)
+            }
+            MultisigInstruction::FinalizeTransactionBuffer() => finalize_transaction_buffer(program_id, accounts),
+            MultisigInstruction::CloseMultisig() => close_multisig(program_id, accounts),
+            MultisigInstruction::ApproveWithSignatures() => approve_with_signatures(program_id, accounts),
+            MultisigInstruction::ProposeConfigTransaction(propose_config_transaction_data) => {
+                propose_config_transaction(program_id, accounts, propose_config_transaction_data)
+            }
+            MultisigInstruction::CloseTransactionBuffer() => close_transaction_buffer(program_id, accounts),
         };
     }
     Err(ProgramError::InvalidInstructionData)
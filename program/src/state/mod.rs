@@ -1,2 +1,16 @@
+use crate::errors::{assert_that, MultisigError};
+use solana_program::entrypoint::ProgramResult;
+
 pub mod multisig;
 pub mod transaction;
+pub mod spending_limit;
+pub mod transaction_buffer;
+pub mod transaction_receipt;
+
+/// Checks the discriminator and layout version every account of this program starts with, so that an account of one
+/// type is never deserialized as another, even if its data happens to parse.
+pub fn check_account_header(data: &[u8], discriminator: u8, version: u8, malformed: MultisigError) -> ProgramResult {
+    assert_that(data.len() >= 2 && data[0] == discriminator, malformed)?;
+    assert_that(data[1] == version, MultisigError::UnsupportedAccountVersion)?;
+    Ok(())
+}
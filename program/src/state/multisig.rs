@@ -1,31 +1,126 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use shank::ShankAccount;
+use shank::{ShankAccount, ShankType};
 use solana_program::pubkey::{Pubkey, PUBKEY_BYTES};
 use solana_program::program_error::ProgramError;
 use crate::errors::{assert_success, MultisigError};
+use crate::state::check_account_header;
+
+/// Allows the owner to propose transactions.
+pub const PERMISSION_PROPOSE: u8 = 1 << 0;
+/// Allows the owner to approve, revoke an approval, reject and vote to cancel transactions.
+pub const PERMISSION_VOTE: u8 = 1 << 1;
+/// Allows the owner to execute approved transactions.
+pub const PERMISSION_EXECUTE: u8 = 1 << 2;
+pub const PERMISSION_ALL: u8 = PERMISSION_PROPOSE | PERMISSION_VOTE | PERMISSION_EXECUTE;
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq, ShankType)]
+pub struct Owner {
+    pub pubkey: Pubkey,
+    pub permissions: u8,
+    // The weight of this owner's votes towards the threshold (only relevant for owners with vote permission).
+    pub weight: u16,
+}
+
+impl Owner {
+    pub const LEN: usize = PUBKEY_BYTES + 1 + 2;
+
+    pub fn has_permission(&self, permission: u8) -> bool {
+        self.permissions & permission == permission
+    }
+
+    pub fn vote_weight(&self) -> u32 {
+        if self.has_permission(PERMISSION_VOTE) { self.weight as u32 } else { 0 }
+    }
+}
 
 #[derive(BorshDeserialize, BorshSerialize, Debug, Clone, ShankAccount)]
 pub struct Multisig {
-    pub owners: Vec<Pubkey>,
-    pub threshold: u8,
+    // Identifies the account as a multisig (see `Multisig::DISCRIMINATOR`).
+    pub discriminator: u8,
+    // The layout version of the account data.
+    pub version: u8,
+    pub owners: Vec<Owner>,
+    // The combined weight of approvals required to execute a transaction.
+    pub threshold: u16,
+    pub cancel_threshold: u8,
     pub nonce: u8,
     pub owner_set_seqno: u32,
+    pub time_lock_seconds: u32,
+    // The index of the most recently proposed transaction (0 if none has been proposed yet).
+    pub transaction_index: u64,
+    // The number of transaction accounts that have been proposed but not yet executed or closed.
+    pub pending_transactions: u32,
+    // The number of spending limit accounts that have been added but not yet removed.
+    pub spending_limits: u32,
     pub padding: Vec<u8>
 }
 
 impl Multisig {
+    pub const DISCRIMINATOR: u8 = 1;
+    // Marks a closed multisig, whose account is kept (rent-exempt, owned by the program) so that no multisig can be
+    // created at its address again to take over its vaults.
+    pub const CLOSED_DISCRIMINATOR: u8 = 6;
+    pub const VERSION: u8 = 1;
+    pub const VAULT_SEED_PREFIX: &'static [u8] = b"vault";
+
+    /// Derives the signer PDA of the given vault of a multisig. Vault 0 is the original multisig signer derived from
+    /// `[multisig, nonce]` and is the only one allowed to change the multisig itself.
+    pub fn find_vault_address(multisig: &Pubkey, vault_index: u8, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[multisig.as_ref(), Self::VAULT_SEED_PREFIX, &[vault_index]], program_id)
+    }
+
     pub fn checked_deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(data, Self::DISCRIMINATOR, Self::VERSION, MultisigError::MalformedMultisigAccount)?;
         assert_success(
             Self::try_from_slice(data),
             MultisigError::MalformedMultisigAccount,
         )
     }
 
+    pub fn owner_index(&self, key: &Pubkey) -> Option<usize> {
+        self.owners.iter().position(|owner| owner.pubkey == *key)
+    }
+
+    /// The number of owners that may vote on transactions, i.e. the upper bound for the cancel threshold.
+    pub fn voter_count(&self) -> u8 {
+        count_voters(&self.owners)
+    }
+
+    /// The combined weight of all voting owners, i.e. the upper bound for the threshold.
+    pub fn total_weight(&self) -> u32 {
+        total_weight(&self.owners)
+    }
+
+    /// The combined weight of the voting owners whose entry in the given per-owner flags (e.g. `Transaction.signers`)
+    /// is set.
+    pub fn vote_weight(&self, votes: &[bool]) -> u32 {
+        self.owners.iter()
+            .zip(votes)
+            .filter(|(_, &voted)| voted)
+            .map(|(owner, _)| owner.vote_weight())
+            .sum()
+    }
+
     pub fn len(&self) -> usize {
-        4 + PUBKEY_BYTES * self.owners.len() +  // owners
-            1 +                                 // threshold
-            1 +                                 // nonce
-            4 +                                 // owner_set_seqno
+        1 +                                   // discriminator
+            1 +                               // version
+            4 + Owner::LEN * self.owners.len() +  // owners
+            2 +                               // threshold
+            1 +                               // cancel_threshold
+            1 +                               // nonce
+            4 +                               // owner_set_seqno
+            4 +                               // time_lock_seconds
+            8 +                               // transaction_index
+            4 +                               // pending_transactions
+            4 +                               // spending_limits
             4 + self.padding.len()  // padding (used to allow re-expansion of owners list)
     }
 }
+
+pub fn count_voters(owners: &[Owner]) -> u8 {
+    owners.iter().filter(|owner| owner.has_permission(PERMISSION_VOTE)).count() as u8
+}
+
+pub fn total_weight(owners: &[Owner]) -> u32 {
+    owners.iter().map(|owner| owner.vote_weight()).sum()
+}
@@ -0,0 +1,81 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankAccount;
+use solana_program::pubkey::{Pubkey, PUBKEY_BYTES};
+use solana_program::program_error::ProgramError;
+use crate::errors::{assert_success, MultisigError};
+use crate::state::check_account_header;
+
+/// Allows its members to transfer up to `amount` lamports (or tokens of `mint`) per period out of a vault of the
+/// multisig without a full proposal.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, ShankAccount)]
+pub struct SpendingLimit {
+    // Identifies the account as a spending limit (see `SpendingLimit::DISCRIMINATOR`).
+    pub discriminator: u8,
+    // The layout version of the account data.
+    pub version: u8,
+    // The multisig account this spending limit belongs to.
+    pub multisig: Pubkey,
+    // The vault the transfers are made from.
+    pub vault_index: u8,
+    // The SPL mint of the transferred tokens (`Pubkey::default()` for lamports).
+    pub mint: Pubkey,
+    // The amount that can be transferred per period.
+    pub amount: u64,
+    // The length of a period.
+    pub period_seconds: i64,
+    // The owners that may use this spending limit.
+    pub members: Vec<Pubkey>,
+    // The amount that can still be transferred in the current period.
+    pub remaining_amount: u64,
+    // Unix timestamp at which the current period started.
+    pub period_started_at: i64,
+}
+
+impl SpendingLimit {
+    pub const DISCRIMINATOR: u8 = 3;
+    pub const VERSION: u8 = 1;
+    pub const SEED_PREFIX: &'static [u8] = b"spending_limit";
+
+    /// Derives the address of the spending limit for the given vault and mint of a multisig.
+    pub fn find_address(multisig: &Pubkey, vault_index: u8, mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[multisig.as_ref(), Self::SEED_PREFIX, &[vault_index], mint.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn checked_deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(data, Self::DISCRIMINATOR, Self::VERSION, MultisigError::MalformedSpendingLimitAccount)?;
+        assert_success(
+            Self::try_from_slice(data),
+            MultisigError::MalformedSpendingLimitAccount,
+        )
+    }
+
+    pub fn is_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    /// Starts a new period (restoring the full amount) once the current one has elapsed. Periods are aligned to the
+    /// creation of the spending limit, so that a late first use does not shift all following periods.
+    pub fn refresh(&mut self, now: i64) {
+        let elapsed_periods = (now - self.period_started_at) / self.period_seconds;
+        if elapsed_periods > 0 {
+            self.period_started_at += elapsed_periods * self.period_seconds;
+            self.remaining_amount = self.amount;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        1 +                                        // discriminator
+            1 +                                    // version
+            32 +                                   // multisig
+            1 +                                    // vault_index
+            32 +                                   // mint
+            8 +                                    // amount
+            8 +                                    // period_seconds
+            4 + PUBKEY_BYTES * self.members.len() +  // members
+            8 +                                    // remaining_amount
+            8                                      // period_started_at
+    }
+}
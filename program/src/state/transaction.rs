@@ -1,35 +1,170 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
+use solana_program::clock::Clock;
+use solana_program::hash::hashv;
 use solana_program::pubkey::Pubkey;
 use solana_program::program_error::ProgramError;
 use crate::errors::{assert_success, MultisigError};
-use crate::instructions::propose_transaction::TransactionInstructionData;
+use crate::instructions::propose_config_transaction::ConfigAction;
+use crate::instructions::propose_transaction::{TransactionExpiry, TransactionInstructionData};
+use crate::state::check_account_header;
+use crate::state::multisig::Multisig;
+use crate::state::transaction_receipt::ExecutionReceipt;
 
 #[derive(BorshDeserialize, BorshSerialize, Debug, Clone, ShankAccount)]
 pub struct Transaction {
+    // Identifies the account as a transaction (see `Transaction::DISCRIMINATOR`).
+    pub discriminator: u8,
+    // The layout version of the account data.
+    pub version: u8,
     // The multisig account this transaction belongs to.
     pub multisig: Pubkey,
+    // The sequential index of this transaction within its multisig (part of the account address).
+    pub transaction_index: u64,
     // The instructions to be executed by this transaction
     pub instructions: Vec<TransactionInstructionData>,
+    // The config actions applied to the multisig instead of instructions (see `propose_config_transaction`).
+    pub config_actions: Vec<ConfigAction>,
+    // The address lookup tables the lookup accounts of the instructions are resolved from.
+    pub address_lookup_tables: Vec<Pubkey>,
+    // The vault whose signer PDA signs the instructions (0 is the multisig signer).
+    pub vault_index: u8,
+    // The number of ephemeral signer PDAs (see `Transaction::find_ephemeral_signer_address`) the instructions use.
+    pub ephemeral_signers: u8,
+    // The number of instructions executed so far by a step-wise execution (see `ExecuteTransactionInstruction`).
+    pub executed_up_to: u16,
+    // receipts[index] records the outcome of executing instructions[index].
+    pub receipts: Vec<ExecutionReceipt>,
     // signers[index] is true iff multisig.owners[index] signed the transaction.
     pub signers: Vec<bool>,
+    // rejectors[index] is true iff multisig.owners[index] rejected the transaction.
+    pub rejectors: Vec<bool>,
+    // revokers[index] is true iff multisig.owners[index] revoked an approval of the transaction, after which signature
+    // approvals (which could be replays of an earlier signature) are no longer accepted from them.
+    pub revokers: Vec<bool>,
+    // Set once enough owners rejected the transaction for the approval threshold to become unreachable.
+    pub rejected: bool,
+    // cancellers[index] is true iff multisig.owners[index] voted to cancel the transaction.
+    pub cancellers: Vec<bool>,
+    // The owner set sequence number the cancel votes were cast under (they are discarded once the owner set changes).
+    pub cancellers_owner_set_seqno: u32,
     // Owner set sequence number.
     pub owner_set_seqno: u32,
+    // Unix timestamp at which the approval threshold was first reached (0 if it has not been reached yet).
+    pub threshold_reached_at: i64,
+    // The owner who proposed the transaction.
+    pub proposer: Pubkey,
+    // The account that paid the rent of the transaction account (the only account the rent is refunded to).
+    pub rent_payer: Pubkey,
+    // The slot in which the transaction was proposed.
+    pub created_at_slot: u64,
+    // The unix timestamp at which the transaction was proposed.
+    pub created_at: i64,
+    // An optional short note by the proposer on the purpose of the transaction.
+    pub memo: Option<String>,
+    // An optional hash of an off-chain document describing the transaction.
+    pub description_hash: Option<[u8; 32]>,
+    // Optional deadline after which the transaction can no longer be approved or executed.
+    pub expiry: Option<TransactionExpiry>,
 }
 
 impl Transaction {
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const VERSION: u8 = 1;
+    pub const SEED_PREFIX: &'static [u8] = b"transaction";
+    pub const EPHEMERAL_SIGNER_SEED_PREFIX: &'static [u8] = b"ephemeral_signer";
+    pub const CONTENT_HASH_PREFIX: &'static [u8] = b"multisig_native_transaction";
+    pub const MAX_MEMO_LEN: usize = 64;
+
+    /// Derives the address of the transaction account with the given index from its multisig.
+    pub fn find_address(multisig: &Pubkey, transaction_index: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[multisig.as_ref(), Self::SEED_PREFIX, &transaction_index.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    /// Derives the address of an ephemeral signer of the given transaction account. `execute_transaction` signs for it,
+    /// so that the instructions can e.g. create new accounts at that address. As transaction accounts are never reused,
+    /// neither are their ephemeral signers.
+    pub fn find_ephemeral_signer_address(transaction: &Pubkey, index: u8, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[transaction.as_ref(), Self::EPHEMERAL_SIGNER_SEED_PREFIX, &[index]],
+            program_id,
+        )
+    }
+
     pub fn checked_deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(data, Self::DISCRIMINATOR, Self::VERSION, MultisigError::MalformedTransactionAccount)?;
         assert_success(
             Self::try_from_slice(data),
             MultisigError::MalformedTransactionAccount,
         )
     }
 
+    /// The canonical hash of everything an owner approves (i.e. all fields but the votes, the proposer, the rent payer
+    /// and the creation time), which can be signed off-chain instead of approving on-chain. It covers the address of the transaction
+    /// account, so that an approval cannot be replayed for another transaction, multisig or deployment of this program.
+    pub fn content_hash(&self, transaction: &Pubkey) -> Result<[u8; 32], ProgramError> {
+        let contents = borsh::to_vec(&(
+            &self.multisig,
+            self.transaction_index,
+            &self.instructions,
+            &self.config_actions,
+            &self.address_lookup_tables,
+            self.vault_index,
+            self.ephemeral_signers,
+            self.owner_set_seqno,
+            &self.expiry,
+            &self.memo,
+            &self.description_hash,
+        ))?;
+        Ok(hashv(&[Self::CONTENT_HASH_PREFIX, transaction.as_ref(), &contents]).to_bytes())
+    }
+
+    pub fn approval_weight(&self, multisig: &Multisig) -> u32 {
+        multisig.vote_weight(&self.signers)
+    }
+
+    pub fn rejection_weight(&self, multisig: &Multisig) -> u32 {
+        multisig.vote_weight(&self.rejectors)
+    }
+
+    pub fn cancellation_count(&self) -> u8 {
+        self.cancellers.iter().filter(|&did_cancel| *did_cancel).count() as u8
+    }
+
+    pub fn is_expired(&self, clock: &Clock) -> bool {
+        self.expiry.as_ref().is_some_and(|expiry| expiry.has_passed(clock))
+    }
+
     pub fn len(&self) -> usize {
+        1 +                                                            // discriminator
+        1 +                                                            // version
         32 +                                                           // multisig
+        8 +                                                            // transaction_index
         4 + self.instructions.iter().map(|instr| instr.len()).sum::<usize>() +  // instructions
+        4 + self.config_actions.iter().map(|action| action.len()).sum::<usize>() +  // config_actions
+        4 + 32 * self.address_lookup_tables.len() +                    // address_lookup_tables
+        1 +                                                            // vault_index
+        1 +                                                            // ephemeral_signers
+        2 +                                                            // executed_up_to
+        4 + ExecutionReceipt::LEN * self.receipts.len() +              // receipts
         4 + self.signers.len() +                                       // signers
-        4                                                              // owner_set_seqno
+        4 + self.rejectors.len() +                                     // rejectors
+        4 + self.revokers.len() +                                      // revokers
+        1 +                                                            // rejected
+        4 + self.cancellers.len() +                                    // cancellers
+        4 +                                                            // cancellers_owner_set_seqno
+        4 +                                                            // owner_set_seqno
+        8 +                                                            // threshold_reached_at
+        32 +                                                           // proposer
+        32 +                                                           // rent_payer
+        8 +                                                            // created_at_slot
+        8 +                                                            // created_at
+        1 + self.memo.as_ref().map_or(0, |memo| 4 + memo.len()) +      // memo
+        1 + self.description_hash.map_or(0, |_| 32) +                  // description_hash
+        1 + self.expiry.as_ref().map_or(0, |expiry| expiry.len())      // expiry
     }
 }
 
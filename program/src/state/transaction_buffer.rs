@@ -0,0 +1,86 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankAccount;
+use solana_program::hash::{Hash, HASH_BYTES};
+use solana_program::pubkey::Pubkey;
+use solana_program::program_error::ProgramError;
+use crate::errors::{assert_success, MultisigError};
+use crate::instructions::propose_transaction::TransactionExpiry;
+use crate::state::check_account_header;
+
+/// Collects the serialized instructions of a transaction that is too large to be proposed in a single instruction. The
+/// buffer is uploaded in chunks and turned into a `Transaction` account once its content matches `final_hash`.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, ShankAccount)]
+pub struct TransactionBuffer {
+    // Identifies the account as a transaction buffer (see `TransactionBuffer::DISCRIMINATOR`).
+    pub discriminator: u8,
+    // The layout version of the account data.
+    pub version: u8,
+    // The multisig account this transaction buffer belongs to.
+    pub multisig: Pubkey,
+    // The owner uploading the buffer (becomes the proposer of the transaction).
+    pub creator: Pubkey,
+    // Distinguishes several buffers of the same creator (part of the account address).
+    pub buffer_index: u8,
+    // The vault whose signer PDA signs the instructions (0 is the multisig signer).
+    pub vault_index: u8,
+    // The number of ephemeral signer PDAs the instructions use.
+    pub ephemeral_signers: u8,
+    // Optional deadline after which the transaction can no longer be approved or executed.
+    pub expiry: Option<TransactionExpiry>,
+    // The address lookup tables the lookup accounts of the instructions refer to.
+    pub address_lookup_tables: Vec<Pubkey>,
+    // An optional short note by the creator on the purpose of the transaction.
+    pub memo: Option<String>,
+    // An optional hash of an off-chain document describing the transaction.
+    pub description_hash: Option<[u8; 32]>,
+    // SHA-256 hash of the complete buffer.
+    pub final_hash: [u8; 32],
+    // Size of the complete buffer in bytes.
+    pub final_size: u32,
+    // The borsh serialized `Vec<TransactionInstructionData>` uploaded so far.
+    pub buffer: Vec<u8>,
+}
+
+impl TransactionBuffer {
+    pub const DISCRIMINATOR: u8 = 4;
+    pub const VERSION: u8 = 1;
+    pub const SEED_PREFIX: &'static [u8] = b"transaction_buffer";
+
+    /// Derives the address of the transaction buffer with the given index of a creator.
+    pub fn find_address(multisig: &Pubkey, creator: &Pubkey, buffer_index: u8, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[multisig.as_ref(), Self::SEED_PREFIX, creator.as_ref(), &[buffer_index]],
+            program_id,
+        )
+    }
+
+    pub fn checked_deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(data, Self::DISCRIMINATOR, Self::VERSION, MultisigError::MalformedTransactionBufferAccount)?;
+        assert_success(
+            Self::try_from_slice(data),
+            MultisigError::MalformedTransactionBufferAccount,
+        )
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.buffer.len() == self.final_size as usize
+            && solana_program::hash::hash(&self.buffer) == Hash::new_from_array(self.final_hash)
+    }
+
+    pub fn len(&self) -> usize {
+        1 +                                                            // discriminator
+        1 +                                                            // version
+        32 +                                                           // multisig
+        32 +                                                           // creator
+        1 +                                                            // buffer_index
+        1 +                                                            // vault_index
+        1 +                                                            // ephemeral_signers
+        1 + self.expiry.as_ref().map_or(0, |expiry| expiry.len()) +    // expiry
+        4 + 32 * self.address_lookup_tables.len() +                    // address_lookup_tables
+        1 + self.memo.as_ref().map_or(0, |memo| 4 + memo.len()) +      // memo
+        1 + self.description_hash.map_or(0, |_| 32) +                  // description_hash
+        HASH_BYTES +                                                   // final_hash
+        4 +                                                            // final_size
+        4 + self.buffer.len()                                          // buffer
+    }
+}
@@ -0,0 +1,54 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::{ShankAccount, ShankType};
+use solana_program::pubkey::Pubkey;
+
+/// The outcome of executing one instruction of a transaction.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Default, PartialEq, ShankType)]
+pub struct ExecutionReceipt {
+    // The index of the instruction within its transaction.
+    pub instruction_index: u16,
+    // SHA-256 of the program id and data returned by the instruction (all zeros if it returned no data).
+    pub return_data_hash: [u8; 32],
+    // The slot the instruction was executed in (0 if it has not been executed yet).
+    pub slot: u64,
+}
+
+impl ExecutionReceipt {
+    pub const LEN: usize = 2 + 32 + 8;
+}
+
+/// Replaces a transaction account once all of its instructions have been executed (or the remaining ones of a partially
+/// executed transaction have been cancelled), as on-chain proof of what was executed (the content hash of the
+/// transaction) and what each instruction returned. Only the rent of the receipt is kept, the rest is refunded.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, ShankAccount)]
+pub struct TransactionReceipt {
+    // Identifies the account as a transaction receipt (see `TransactionReceipt::DISCRIMINATOR`).
+    pub discriminator: u8,
+    // The layout version of the account data.
+    pub version: u8,
+    // The multisig account the executed transaction belonged to.
+    pub multisig: Pubkey,
+    // The index of the executed transaction within its multisig (the receipt keeps the address of the transaction).
+    pub transaction_index: u64,
+    // The content hash of the executed transaction (see `Transaction::content_hash`).
+    pub content_hash: [u8; 32],
+    // The owner who executed the (last instructions of the) transaction, or who cancelled its remaining instructions.
+    pub executor: Pubkey,
+    // One receipt per instruction of the transaction.
+    pub receipts: Vec<ExecutionReceipt>,
+}
+
+impl TransactionReceipt {
+    pub const DISCRIMINATOR: u8 = 5;
+    pub const VERSION: u8 = 1;
+
+    pub fn len(&self) -> usize {
+        1 +                                                   // discriminator
+            1 +                                               // version
+            32 +                                              // multisig
+            8 +                                               // transaction_index
+            32 +                                              // content_hash
+            32 +                                              // executor
+            4 + ExecutionReceipt::LEN * self.receipts.len()   // receipts
+    }
+}
@@ -2,6 +2,7 @@ import {describe, test} from "node:test";
 import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
 import {start} from "solana-bankrun";
 import {MultisigDsl} from "../ts";
+import {Buffer} from "node:buffer";
 import {assert} from "chai";
 
 describe("account type confusion", async () => {
@@ -16,7 +17,7 @@ describe("account type confusion", async () => {
     const [ownerA] = multisig1.owners;
 
     // Type confusion: pass multisig2's account where a Transaction account is expected.
-    // The Borsh deserialization will fail because the data layouts are structurally incompatible.
+    // This is rejected because of the account discriminator, regardless of whether the data would parse.
     const txMeta = await dsl.approveTransaction(ownerA, multisig1.address, multisig2.address);
 
     assert.isNotNull(txMeta.result);
@@ -42,4 +43,26 @@ describe("account type confusion", async () => {
     assert.ok(txMeta.meta.logMessages.some(log => log.includes("MalformedMultisigAccount")));
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x12");
   });
+
+  await test("should reject a Multisig account with an unsupported layout version", async () => {
+    const multisig = await dsl.createMultisig(1, 2);
+    const [ownerA] = multisig.owners;
+    const transferIx = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _] = await dsl.proposeTransaction(ownerA, [transferIx], multisig.address);
+
+    // Bump the version byte that follows the discriminator, as a future layout would.
+    const multisigAccount = await context.banksClient.getAccount(multisig.address);
+    const data = Buffer.from(multisigAccount.data);
+    data[1] = 2;
+    context.setAccount(multisig.address, {...multisigAccount, data: data});
+
+    const txMeta = await dsl.approveTransaction(ownerA, multisig.address, transactionAddress);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: UnsupportedAccountVersion (The layout version of the given account is not supported by this program.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x1c");
+  });
 });
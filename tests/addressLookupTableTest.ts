@@ -0,0 +1,116 @@
+import {describe, test} from "node:test";
+import {AddressLookupTableAccount, Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("address lookup tables", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  function payouts(multisigSigner: PublicKey, recipients: PublicKey[]) {
+    return recipients.map(recipient => SystemProgram.transfer({
+      fromPubkey: multisigSigner,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    }));
+  }
+
+  await test("should resolve lookup accounts from an address lookup table on execution", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const recipients = Array.from({length: 8}, () => Keypair.generate().publicKey);
+    const addressLookupTable = dsl.createAddressLookupTable(recipients);
+    const instructions = payouts(multisig.signer, recipients);
+
+    // without the lookup table, the proposal would exceed the packet size
+    const [transactionAddress, proposeMeta] = await dsl.proposeTransaction(
+        ownerA, instructions, multisig.address, {addressLookupTables: [addressLookupTable]});
+
+    assert.isNull(proposeMeta.result);
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.deepStrictEqual(transaction["address_lookup_tables"], [Array.from(addressLookupTable.key.toBytes())]);
+    assert.strictEqual(transaction["instructions"][0].accounts.length, 1);
+    assert.deepStrictEqual(transaction["instructions"][0].lookup_accounts, [{position: 1, table_index: 0, address_index: 0, is_writable: true}]);
+
+    const executeMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, instructions, multisig.signer, multisig.address, ownerA, context.payer.publicKey, [addressLookupTable]);
+
+    assert.isNull(executeMeta.result);
+    for (const recipient of recipients) {
+      await dsl.assertBalance(recipient, 1_000_000);
+    }
+  });
+
+  await test("should not execute a transaction without its address lookup table", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const addressLookupTable = dsl.createAddressLookupTable([recipient]);
+    const instructions = payouts(multisig.signer, [recipient]);
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(
+        ownerA, instructions, multisig.address, {addressLookupTables: [addressLookupTable]});
+
+    const executeMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, instructions, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(executeMeta.result, "Error processing Instruction 0: custom program error: 0x27");
+    assert(executeMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidAddressLookupTable (A lookup account refers to a missing address lookup table, address or position.)")));
+    assert.strictEqual(await context.banksClient.getAccount(recipient), null);
+  });
+
+  await test("should not propose lookup accounts from an account that is not an address lookup table", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const fakeAddressLookupTable = dsl.createAddressLookupTable([recipient], SystemProgram.programId);
+    const instructions = payouts(multisig.signer, [recipient]);
+
+    const [transactionAddress, proposeMeta] = await dsl.proposeTransaction(
+        ownerA, instructions, multisig.address, {addressLookupTables: [fakeAddressLookupTable]});
+
+    assert.strictEqual(proposeMeta.result, "Error processing Instruction 0: custom program error: 0x27");
+    assert.strictEqual(await context.banksClient.getAccount(transactionAddress), null);
+  });
+
+  await test("should not propose a lookup account beyond the end of its address lookup table", async () => {
+    const multisig = await dsl.createMultisig(2, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const addressLookupTable = dsl.createAddressLookupTable([recipient]);
+    // the proposal refers to an address the table authority could only append after the owners approved it
+    const attacker = Keypair.generate().publicKey;
+    const anticipatedTable = new AddressLookupTableAccount({
+      key: addressLookupTable.key,
+      state: {...addressLookupTable.state, addresses: [recipient, attacker]},
+    });
+
+    const [transactionAddress, proposeMeta] = await dsl.proposeTransaction(
+        ownerA, payouts(multisig.signer, [attacker]), multisig.address, {addressLookupTables: [anticipatedTable]});
+
+    assert.strictEqual(proposeMeta.result, "Error processing Instruction 0: custom program error: 0x27");
+    assert(proposeMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidAddressLookupTable (A lookup account refers to a missing address lookup table, address or position.)")));
+    assert.strictEqual(await context.banksClient.getAccount(transactionAddress), null);
+  });
+
+  await test("should execute the approved addresses even if the address lookup table is extended after approval", async () => {
+    const multisig = await dsl.createMultisig(2, 2, 10_000_000);
+    const [ownerA, ownerB] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const addressLookupTable = dsl.createAddressLookupTable([recipient]);
+    const instructions = payouts(multisig.signer, [recipient]);
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(
+        ownerA, instructions, multisig.address, {addressLookupTables: [addressLookupTable]});
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+
+    const attacker = Keypair.generate().publicKey;
+    const extendedTable = await dsl.extendAddressLookupTable(addressLookupTable, [attacker]);
+    const executeMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, instructions, multisig.signer, multisig.address, ownerA, context.payer.publicKey, [extendedTable]);
+
+    assert.isNull(executeMeta.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+    assert.strictEqual(await context.banksClient.getAccount(attacker), null);
+  });
+});
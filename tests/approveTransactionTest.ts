@@ -1,9 +1,9 @@
 import {describe, test} from "node:test";
 import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
 import {start} from "solana-bankrun";
-import {MultisigDsl, MultisigSchema} from "../ts";
+import {MultisigDsl, MultisigSchema, Permission} from "../ts";
 import {assert} from "chai";
-import {Transaction, TransactionSchema} from "../ts/state/transaction";
+import {Transaction, transactionContentHash, TransactionSchema} from "../ts/state/transaction";
 import {Buffer} from "node:buffer";
 import * as borsh from "borsh";
 
@@ -26,7 +26,7 @@ describe("approve transaction", async () => {
 
     const logs = txMeta.meta.logMessages;
     assert(logs[0].startsWith(`Program ${programId}`));
-    assert(logs.some(log => log === "Program log: invoke approve_transaction"));
+    assert(logs.some(log => log === "Program log: invoke approve_transaction - ApproveTransactionInstruction { expected_hash: None }"));
     assert(logs.some(log => log === `Program ${programId} success`));
 
     let transactionAccount: Transaction = await dsl.getTransactionAccount(transactionAddress);
@@ -45,7 +45,7 @@ describe("approve transaction", async () => {
     });
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
 
-    const txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: NotEnoughSigners (The transaction must reach a minimum number of approvals.)"))
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0xd");
@@ -68,7 +68,7 @@ describe("approve transaction", async () => {
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
 
-    await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     await dsl.assertBalance(multisig.signer, 0);
   });
@@ -88,7 +88,7 @@ describe("approve transaction", async () => {
     //Approve again with the same owner meaning still only 1/3 approval
     await dsl.approveTransaction(ownerA, multisig.address, txAddress);
 
-    const txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: NotEnoughSigners (The transaction must reach a minimum number of approvals.)"))
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0xd");
@@ -144,6 +144,42 @@ describe("approve transaction", async () => {
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0xa");
   });
 
+  await test("should approve if the content hash matches the expected hash", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    const expectedHash = transactionContentHash(transactionAddress, await dsl.getTransactionAccount(transactionAddress));
+
+    const txResult = await dsl.approveTransaction(ownerB, multisig.address, transactionAddress, true, true, expectedHash);
+
+    assert.isNull(txResult.result);
+    assert.deepStrictEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [true, true, false]);
+  });
+
+  await test("should not approve a transaction with identical instructions at a different address than reviewed", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const [reviewedAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    const [otherAddress, _otherTxMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    const reviewedHash = transactionContentHash(reviewedAddress, await dsl.getTransactionAccount(reviewedAddress));
+
+    const txResult = await dsl.approveTransaction(ownerB, multisig.address, otherAddress, true, true, reviewedHash);
+
+    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: ContentHashMismatch (The content hash of the transaction does not match the expected hash.)"));
+    assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x2e");
+    assert.deepStrictEqual((await dsl.getTransactionAccount(otherAddress))["signers"], [true, false, false]);
+  });
+
   await test("should not allow non owner to approve", async () => {
     const multisig = await dsl.createMultisig(2, 3, 1_000_000);
     const [ownerA, _ownerB, _ownerC] = multisig.owners;
@@ -171,10 +207,17 @@ describe("approve transaction", async () => {
     const fakeMultisigAddress = Keypair.generate().publicKey;
     const fakeMultisigData = Buffer.from(
         borsh.serialize(MultisigSchema, {
-          owners: [attacker.publicKey.toBytes()],
+          discriminator: 1,
+          version: 1,
+          owners: [{pubkey: attacker.publicKey.toBytes(), permissions: Permission.All, weight: 1}],
           threshold: 1,
+          cancel_threshold: 1,
           nonce: 0,
           owner_set_seqno: 0,
+          time_lock_seconds: 0,
+          transaction_index: 0,
+          pending_transactions: 0,
+          spending_limits: 0,
           padding: [],
         }),
     );
@@ -191,10 +234,32 @@ describe("approve transaction", async () => {
     const fakeTxAddress = Keypair.generate().publicKey;
     const fakeTxData = Buffer.from(
         borsh.serialize(TransactionSchema, {
+          discriminator: 2,
+          version: 1,
           multisig: fakeMultisigAddress.toBytes(),
+          transaction_index: 1,
           instructions: [],
+          config_actions: [],
+          address_lookup_tables: [],
+          vault_index: 0,
+          ephemeral_signers: 0,
+          executed_up_to: 0,
+          receipts: [],
           signers: [false],
+          rejectors: [false],
+          revokers: [false],
+          rejected: false,
+          cancellers: [false],
+          cancellers_owner_set_seqno: 0,
           owner_set_seqno: 0,
+          threshold_reached_at: 0,
+          proposer: PublicKey.default.toBytes(),
+          rent_payer: PublicKey.default.toBytes(),
+          created_at_slot: 0,
+          created_at: 0,
+          memo: null,
+          description_hash: null,
+          expiry: null,
         }),
     );
     context.setAccount(fakeTxAddress, {
@@ -224,10 +289,32 @@ describe("approve transaction", async () => {
     const fakeTxAddress = Keypair.generate().publicKey;
     const fakeTxData = Buffer.from(
         borsh.serialize(TransactionSchema, {
+          discriminator: 2,
+          version: 1,
           multisig: multisig.address.toBytes(),
+          transaction_index: 1,
           instructions: [],
+          config_actions: [],
+          address_lookup_tables: [],
+          vault_index: 0,
+          ephemeral_signers: 0,
+          executed_up_to: 0,
+          receipts: [],
           signers: [true, false, false],
+          rejectors: [false, false, false],
+          revokers: [false, false, false],
+          rejected: false,
+          cancellers: [false, false, false],
+          cancellers_owner_set_seqno: 0,
           owner_set_seqno: 0,
+          threshold_reached_at: 0,
+          proposer: PublicKey.default.toBytes(),
+          rent_payer: PublicKey.default.toBytes(),
+          created_at_slot: 0,
+          created_at: 0,
+          memo: null,
+          description_hash: null,
+          expiry: null,
         }),
     );
     context.setAccount(fakeTxAddress, {
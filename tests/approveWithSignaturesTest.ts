@@ -0,0 +1,80 @@
+import {describe, test} from "node:test";
+import {Ed25519Program, Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {createApproveWithSignaturesInstruction, MultisigDsl} from "../ts";
+import {transactionContentHash} from "../ts/state/transaction";
+import {assert} from "chai";
+
+describe("approve with signatures", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should approve for all owners who signed the content hash off-chain", async () => {
+    const multisig = await dsl.createMultisig(3, 4, 10_000_000);
+    const [ownerA, ownerB, ownerC, _ownerD] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: recipient});
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+
+    const approveMeta = await dsl.approveWithSignatures([ownerB, ownerC], multisig.address, transactionAddress);
+    const executeMeta = await dsl.executeTransaction(transactionAddress, transfer, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(approveMeta.result);
+    assert.isNull(executeMeta.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
+  await test("should not approve with a signature over another message", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+    const [otherTransactionAddress, _otherProposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+    // a signature for another transaction with the same instructions must not be replayable
+    const otherContentHash = transactionContentHash(otherTransactionAddress, await dsl.getTransactionAccount(otherTransactionAddress));
+
+    const signature = Ed25519Program.createInstructionWithPrivateKey({privateKey: ownerB.secretKey, message: otherContentHash});
+    const approve = createApproveWithSignaturesInstruction(multisig.address, transactionAddress, programId);
+    const txMeta = await dsl.createAndProcessTx([signature, approve], context.payer);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 1: custom program error: 0x29");
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: MissingSignatureApproval (No ed25519 signature over the transaction content hash by an owner allowed to vote was found.)")));
+    assert.deepStrictEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [true, false, false]);
+  });
+
+  await test("should not approve with the signature of a non-owner", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+
+    const txMeta = await dsl.approveWithSignatures([Keypair.generate()], multisig.address, transactionAddress);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 1: custom program error: 0x29");
+  });
+
+  await test("should not let a replayed signature undo a revoked approval or a rejection", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+    const contentHash = transactionContentHash(transactionAddress, await dsl.getTransactionAccount(transactionAddress));
+    const signatures = [ownerB, ownerC].map(owner =>
+        Ed25519Program.createInstructionWithPrivateKey({privateKey: owner.secretKey, message: contentHash}));
+    const approve = createApproveWithSignaturesInstruction(multisig.address, transactionAddress, programId);
+    await dsl.createAndProcessTx([...signatures, approve], context.payer);
+
+    await dsl.revokeApproval(ownerB, multisig.address, transactionAddress);
+    await dsl.rejectTransaction(ownerC, multisig.address, transactionAddress);
+    const currentSlot = await context.banksClient.getSlot();
+    context.warpToSlot(currentSlot + 1n);  // avoid posting the same TX in the same block
+    const replayMeta = await dsl.createAndProcessTx([...signatures, approve], context.payer);
+
+    assert.strictEqual(replayMeta.result, "Error processing Instruction 2: custom program error: 0x29");
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.deepStrictEqual(transaction["signers"], [true, false, false]);
+    assert.deepStrictEqual(transaction["rejectors"], [false, false, true]);
+    assert.deepStrictEqual(transaction["revokers"], [false, true, false]);
+  });
+});
@@ -1,7 +1,7 @@
 import {describe, test} from "node:test";
 import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
 import {start} from "solana-bankrun";
-import {MultisigDsl, MultisigSchema} from "../ts";
+import {MultisigDsl, MultisigSchema, Permission} from "../ts";
 import {assert} from "chai";
 import {TransactionSchema} from "../ts/state/transaction";
 import {Buffer} from "node:buffer";
@@ -21,19 +21,22 @@ describe("cancel transaction", async () => {
       lamports: 1_000_000,
       toPubkey: context.payer.publicKey,
     });
-    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    const rentPayer = Keypair.generate();
+    await dsl.fund(rentPayer.publicKey, 3_368_640);
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address,
+        {rentPayer});
 
-    await dsl.assertBalance(ownerA.publicKey, 0);
+    await dsl.assertBalance(rentPayer.publicKey, 0);
 
-    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, rentPayer.publicKey);
 
-    await dsl.assertBalance(ownerA.publicKey, 2_053_200); // this is the rent exemption amount
+    await dsl.assertBalance(rentPayer.publicKey, 3_368_640); // this is the rent exemption amount
 
     const transactionAccountInfo = await dsl.programTestContext.banksClient.getAccount(transactionAddress, "confirmed");
     assert.strictEqual(transactionAccountInfo, null);
   });
 
-  await test("should let owner cancel transaction, even if the owner set has changed", async () => {
+  await test("should only refund the rent payer of the transaction", async () => {
     const multisig = await dsl.createMultisig(2, 3);
     const [ownerA, ownerB, _ownerC] = multisig.owners;
 
@@ -44,22 +47,130 @@ describe("cancel transaction", async () => {
     });
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
 
+    const txMeta = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, ownerB.publicKey);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: RentPayerMismatch (The given account does not match the rent payer of the transaction.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x2c");
+    assert.notEqual(await context.banksClient.getAccount(transactionAddress, "confirmed"), null);
+  });
+
+  await test("should let owner cancel transaction, even if the owner set has changed", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const rentPayer = Keypair.generate();
+    await dsl.fund(rentPayer.publicKey, 3_368_640);
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address,
+        {rentPayer});
+
     // Change owner set of the multisig while the TX account at transactionAddress is still pending
     const newOwners = [ownerA.publicKey, ownerB.publicKey, Keypair.generate().publicKey];
     const changeOwnersInstruction = dsl.createSetOwnersInstruction(multisig, newOwners);
     const [changedOwnersAddress, _txMeta2]= await dsl.proposeTransaction(ownerA, [changeOwnersInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, changedOwnersAddress);
-    await dsl.executeTransaction(changedOwnersAddress, changeOwnersInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(changedOwnersAddress, changeOwnersInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     // Now cancel the original transaction instruction (the corresponding TX account owner set will be outdated at this point)
-    await dsl.assertBalance(ownerB.publicKey, 0);
-    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, ownerB.publicKey);
-    await dsl.assertBalance(ownerB.publicKey, 2_053_200); // this is the rent exemption amount
+    await dsl.assertBalance(rentPayer.publicKey, 0);
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, rentPayer.publicKey);
+    await dsl.assertBalance(rentPayer.publicKey, 3_368_640); // this is the rent exemption amount
 
     const transactionAccountInfo = await dsl.programTestContext.banksClient.getAccount(transactionAddress, "confirmed");
     assert.strictEqual(transactionAccountInfo, null);
   });
 
+  await test("should require cancel threshold of owners to cancel a transaction", async () => {
+    const multisig = await dsl.createMultisigWithCancelThreshold(2, 3, 2);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const rentPayer = Keypair.generate();
+    await dsl.fund(rentPayer.publicKey, 3_368_640);
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address,
+        {rentPayer});
+
+    const firstCancel = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, rentPayer.publicKey);
+    assert.isNull(firstCancel.result);
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["cancellers"], [false, true, false]);
+    await dsl.assertBalance(rentPayer.publicKey, 0);
+
+    // a repeated vote of the same owner does not count twice
+    context.warpToSlot((await context.banksClient.getSlot()) + 1n);
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, rentPayer.publicKey);
+    assert.notEqual(await context.banksClient.getAccount(transactionAddress, "confirmed"), null);
+
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerC, rentPayer.publicKey);
+    await dsl.assertBalance(rentPayer.publicKey, 3_368_640); // this is the rent exemption amount
+    assert.strictEqual(await context.banksClient.getAccount(transactionAddress, "confirmed"), null);
+  });
+
+  await test("should let proposer withdraw their own transaction regardless of cancel threshold", async () => {
+    const multisig = await dsl.createMultisigWithCancelThreshold(2, 3, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const txMeta = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    assert.strictEqual(await context.banksClient.getAccount(transactionAddress, "confirmed"), null);
+  });
+
+  await test("should require cancel threshold of owners to cancel a partially executed transaction, even its proposer", async () => {
+    const [ownerA, ownerB, ownerC] = Array.from({length: 3}, (_, _n) => Keypair.generate());
+    const multisig = await dsl.createMultisigWithOwners(1, [ownerA, ownerB, ownerC], {cancelThreshold: 2, initialBalance: 10_000_000});
+    const recipient = Keypair.generate().publicKey;
+    const transfers = [1, 2].map(_ => SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: recipient}));
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, transfers, multisig.address);
+    await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, [transfers[0]], multisig.signer, multisig.address, ownerA, context.payer.publicKey, [], 1);
+
+    const proposerCancel = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerA, context.payer.publicKey);
+    assert.isNull(proposerCancel.result);
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["cancellers"], [true, false, false]);
+
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, context.payer.publicKey);
+    const receipt = await dsl.getTransactionReceipt(transactionAddress);
+    assert.notStrictEqual(receipt["receipts"][0].slot, 0n);
+    assert.strictEqual(receipt["receipts"][1].slot, 0n);
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["pending_transactions"], 0);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
+  await test("should require cancel threshold of the new owners to cancel a transaction that changed the owners midway", async () => {
+    const [ownerA, ownerB, ownerC, ownerD] = Array.from({length: 4}, (_, _n) => Keypair.generate());
+    const multisig = await dsl.createMultisigWithOwners(1, [ownerA, ownerB, ownerC], {cancelThreshold: 2, initialBalance: 10_000_000});
+    const setOwners = dsl.createSetOwnersInstruction(multisig, [ownerA.publicKey, ownerB.publicKey, ownerD.publicKey]);
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners, transfer], multisig.address);
+    await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, [setOwners], multisig.signer, multisig.address, ownerA, context.payer.publicKey, [], 1);
+
+    // the owner set of the transaction is outdated now, but a single owner may not cancel it anymore
+    const firstCancel = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, context.payer.publicKey);
+    assert.isNull(firstCancel.result);
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["cancellers"], [false, true, false]);
+
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerD, context.payer.publicKey);
+    const receipt = await dsl.getTransactionReceipt(transactionAddress);
+    assert.strictEqual(receipt["receipts"].length, 2);
+    assert.strictEqual(receipt["receipts"][1].slot, 0n);
+  });
+
   await test("should not let a non-owner cancel transaction", async () => {
     const multisig = await dsl.createMultisig(2, 3);
     const [ownerA, _ownerB, _ownerC] = multisig.owners;
@@ -71,7 +182,7 @@ describe("cancel transaction", async () => {
       toPubkey: context.payer.publicKey,
     });
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
-    const txResult = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerD, ownerA.publicKey);
+    const txResult = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerD, context.payer.publicKey);
 
     assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: InvalidExecutor (The executor must be a signer and an owner of this multisig.)"))
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x8");
@@ -92,9 +203,9 @@ describe("cancel transaction", async () => {
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
-    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, context.payer.publicKey);
 
-    const txResult = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, ownerA.publicKey);
+    const txResult = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
 
     assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: AccountOwnedByWrongProgram (The given account is owned by a different program than expected.)"));
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x13");
@@ -111,7 +222,7 @@ describe("cancel transaction", async () => {
     });
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
 
-    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, context.payer.publicKey);
 
     let txResult = await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
@@ -131,11 +242,11 @@ describe("cancel transaction", async () => {
     });
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
 
-    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerB, context.payer.publicKey);
 
     const [transactionAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress2);
-    await dsl.executeTransaction(transactionAddress2, transactionInstruction, multisig.signer, multisig.address, ownerA, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress2, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
 
     await dsl.assertBalance(recipient.publicKey, 1_000_000);
   })
@@ -170,10 +281,17 @@ describe("cancel transaction", async () => {
     const fakeMultisigAddress = Keypair.generate().publicKey;
     const fakeMultisigData = Buffer.from(
         borsh.serialize(MultisigSchema, {
-          owners: [attacker.publicKey.toBytes()],
+          discriminator: 1,
+          version: 1,
+          owners: [{pubkey: attacker.publicKey.toBytes(), permissions: Permission.All, weight: 1}],
           threshold: 1,
+          cancel_threshold: 1,
           nonce: 0,
           owner_set_seqno: 0,
+          time_lock_seconds: 0,
+          transaction_index: 0,
+          pending_transactions: 0,
+          spending_limits: 0,
           padding: [],
         }),
     );
@@ -188,10 +306,32 @@ describe("cancel transaction", async () => {
     const fakeTxAddress = Keypair.generate().publicKey;
     const fakeTxData = Buffer.from(
         borsh.serialize(TransactionSchema, {
+          discriminator: 2,
+          version: 1,
           multisig: fakeMultisigAddress.toBytes(),
+          transaction_index: 1,
           instructions: [],
+          config_actions: [],
+          address_lookup_tables: [],
+          vault_index: 0,
+          ephemeral_signers: 0,
+          executed_up_to: 0,
+          receipts: [],
           signers: [false],
+          rejectors: [false],
+          revokers: [false],
+          rejected: false,
+          cancellers: [false],
+          cancellers_owner_set_seqno: 0,
           owner_set_seqno: 0,
+          threshold_reached_at: 0,
+          proposer: PublicKey.default.toBytes(),
+          rent_payer: PublicKey.default.toBytes(),
+          created_at_slot: 0,
+          created_at: 0,
+          memo: null,
+          description_hash: null,
+          expiry: null,
         }),
     );
     context.setAccount(fakeTxAddress, {
@@ -221,10 +361,32 @@ describe("cancel transaction", async () => {
     const fakeTxAddress = Keypair.generate().publicKey;
     const fakeTxData = Buffer.from(
         borsh.serialize(TransactionSchema, {
+          discriminator: 2,
+          version: 1,
           multisig: multisig.address.toBytes(),
+          transaction_index: 1,
           instructions: [],
+          config_actions: [],
+          address_lookup_tables: [],
+          vault_index: 0,
+          ephemeral_signers: 0,
+          executed_up_to: 0,
+          receipts: [],
           signers: [true, false, false],
+          rejectors: [false, false, false],
+          revokers: [false, false, false],
+          rejected: false,
+          cancellers: [false, false, false],
+          cancellers_owner_set_seqno: 0,
           owner_set_seqno: 0,
+          threshold_reached_at: 0,
+          proposer: PublicKey.default.toBytes(),
+          rent_payer: PublicKey.default.toBytes(),
+          created_at_slot: 0,
+          created_at: 0,
+          memo: null,
+          description_hash: null,
+          expiry: null,
         }),
     );
     context.setAccount(fakeTxAddress, {
@@ -234,7 +396,7 @@ describe("cancel transaction", async () => {
       executable: false,
     });
 
-    const txMeta = await dsl.cancelTransaction(fakeTxAddress, multisig.address, ownerB, ownerA.publicKey);
+    const txMeta = await dsl.cancelTransaction(fakeTxAddress, multisig.address, ownerB, context.payer.publicKey);
 
     assert.ok(
         txMeta.meta.logMessages.some(log => log.includes("AccountOwnedByWrongProgram")),
@@ -0,0 +1,54 @@
+import {describe, test} from "node:test";
+import {PublicKey} from "@solana/web3.js";
+import {assert} from "chai";
+import {start} from "solana-bankrun";
+import {MultisigDsl} from "../ts";
+
+describe("change cancel threshold", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should change cancel threshold of multisig", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const changeCancelThreshold = dsl.createChangeCancelThresholdInstruction(multisig, 2);
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeCancelThreshold], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+    await dsl.executeTransaction(txAddress, changeCancelThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.strictEqual(actualMultisig["cancel_threshold"], 2);
+    assert.strictEqual(actualMultisig["threshold"], 2);
+  });
+
+  await test("should not allow cancel threshold greater than number of owners", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const changeCancelThreshold = dsl.createChangeCancelThresholdInstruction(multisig, 4);
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeCancelThreshold], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+    const txResult = await dsl.executeTransaction(txAddress, changeCancelThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: InvalidCancelThreshold (Cancel threshold must be less than or equal to the number of voting owners and greater than zero.)"));
+    assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x18");
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["cancel_threshold"], 1);
+  });
+
+  await test("should stop a single owner from cancelling a proposal to remove them", async () => {
+    const multisig = await dsl.createMultisigWithCancelThreshold(2, 3, 2);
+    const [ownerA, ownerB, compromisedOwner] = multisig.owners;
+
+    const setOwners = dsl.createSetOwnersInstruction(multisig, [ownerA.publicKey, ownerB.publicKey]);
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
+    await dsl.cancelTransaction(txAddress, multisig.address, compromisedOwner, context.payer.publicKey);
+
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+    const txResult = await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    assert.isNull(txResult.result);
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["owners"].length, 2);
+  });
+});
@@ -17,12 +17,12 @@ describe("change threshold", async () => {
     const changeThreshold = dsl.createChangeThresholdInstruction(multisig, 3);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(actualMultisig["nonce"], multisig.nonce);
     assert.strictEqual(actualMultisig["threshold"], 3);
-    assert.deepEqual(actualMultisig["owners"], multisig.owners.map(owner => Array.from(owner.publicKey.toBytes())));
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), multisig.owners.map(owner => Array.from(owner.publicKey.toBytes())));
     assert.strictEqual(actualMultisig["owner_set_seqno"], 0);
   });
 
@@ -33,20 +33,20 @@ describe("change threshold", async () => {
     const changeThresholdTo3 = dsl.createChangeThresholdInstruction(multisig, 3);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeThresholdTo3], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, changeThresholdTo3, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, changeThresholdTo3, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const changeThresholdTo2 = dsl.createChangeThresholdInstruction(multisig, 2);
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [changeThresholdTo2], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress2);
 
     // Fail when trying to execute with the old threshold
-    let txResult = await dsl.executeTransaction(txAddress2, changeThresholdTo2, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    let txResult = await dsl.executeTransaction(txAddress2, changeThresholdTo2, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: NotEnoughSigners (The transaction must reach a minimum number of approvals.)"))
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0xd");
 
     //Succeed when reaching the new threshold
     await dsl.approveTransaction(ownerC, multisig.address, txAddress2);
-    await dsl.executeTransaction(txAddress2, changeThresholdTo2, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress2, changeThresholdTo2, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     const actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(actualMultisig["threshold"], 2);
   });
@@ -64,7 +64,7 @@ describe("change threshold", async () => {
 
     // so we have 2 approvals
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     //The already existing transaction has now been executed and should update the threshold to 1
     let updatedThreshold = (await dsl.getMultisig(multisig.address))["threshold"];
@@ -82,14 +82,14 @@ describe("change threshold", async () => {
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [changeThresholdTo3], multisig.address);
 
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, changeThresholdTo1, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, changeThresholdTo1, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     // threshold now updated to 1
     let updatedThreshold = (await dsl.getMultisig(multisig.address))["threshold"];
     assert.strictEqual(updatedThreshold, 1);
 
     // threshold should now be set to 1 meaning that transaction 2 has met the 1/3 approval required for execution, and does not need a second approval
-    await dsl.executeTransaction(txAddress2, changeThresholdTo3, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress2, changeThresholdTo3, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     // the second threshold update transaction has now been executed and should update the threshold to 3
     let updatedThreshold2 = (await dsl.getMultisig(multisig.address))["threshold"];
@@ -103,8 +103,8 @@ describe("change threshold", async () => {
     const changeThreshold = dsl.createChangeThresholdInstruction(multisig, 0);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    let txResult = await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
-    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the number of owners and greater than zero.)"))
+    let txResult = await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the total weight of the voting owners and greater than zero.)"))
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x0");
 
     let actualThreshold = (await dsl.getMultisig(multisig.address))["threshold"];
@@ -118,8 +118,8 @@ describe("change threshold", async () => {
     const changeThreshold = dsl.createChangeThresholdInstruction(multisig, 4);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    let txResult = await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
-    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the number of owners and greater than zero.)"))
+    let txResult = await dsl.executeTransaction(txAddress, changeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the total weight of the voting owners and greater than zero.)"))
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x0");
 
     let actualThreshold = (await dsl.getMultisig(multisig.address))["threshold"];
@@ -145,13 +145,13 @@ describe("change threshold", async () => {
     const changeThresholdUsingPayer = dsl.createChangeThresholdInstructionManualSigner(dsl.programTestContext.payer.publicKey, multisig.address, 1);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeThresholdUsingPayer], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    let txResult = await dsl.executeTransaction(txAddress, changeThresholdUsingPayer, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    let txResult = await dsl.executeTransaction(txAddress, changeThresholdUsingPayer, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     assert.strictEqual(txResult.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
 
     const changeThresholdUsingOwner = dsl.createChangeThresholdInstructionManualSigner(ownerC.publicKey, multisig.address, 1);
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [changeThresholdUsingOwner], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress2);
-    let txResult2 = await dsl.executeTransaction(txAddress2, changeThresholdUsingOwner, multisig.signer, multisig.address, ownerC, ownerA.publicKey);
+    let txResult2 = await dsl.executeTransaction(txAddress2, changeThresholdUsingOwner, multisig.signer, multisig.address, ownerC, context.payer.publicKey);
     assert.strictEqual(txResult2.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
   });
 
@@ -0,0 +1,112 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {assert} from "chai";
+import {start} from "solana-bankrun";
+import {MultisigDsl} from "../ts";
+import {fail} from "node:assert";
+
+describe("change time lock", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should change time lock of multisig", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const changeTimeLock = dsl.createChangeTimeLockInstruction(multisig, 3600);
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [changeTimeLock], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+    await dsl.executeTransaction(txAddress, changeTimeLock, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.strictEqual(actualMultisig["time_lock_seconds"], 3600);
+    assert.strictEqual(actualMultisig["threshold"], 2);
+    assert.strictEqual(actualMultisig["owner_set_seqno"], 0);
+  });
+
+  await test("should record when the approval threshold is reached", async () => {
+    const multisig = await dsl.createMultisigWithTimeLock(2, 3, 3600);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    assert.strictEqual((await dsl.getTransactionAccount(txAddress))["threshold_reached_at"], 0n);
+
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+    const clock = await context.banksClient.getClock();
+    assert.strictEqual((await dsl.getTransactionAccount(txAddress))["threshold_reached_at"], clock.unixTimestamp);
+  });
+
+  await test("should not execute transaction before time lock has elapsed", async () => {
+    const multisig = await dsl.createMultisigWithTimeLock(2, 3, 3600, 1_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    });
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+
+    let txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: TimeLockNotElapsed (The time lock of the transaction has not elapsed since the approval threshold was reached.)"));
+    assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x14");
+    await dsl.assertBalance(multisig.signer, 1_000_000);
+
+    await dsl.advanceClock(3599);
+    txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x14");
+
+    await dsl.advanceClock(1);
+    txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.isNull(txResult.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
+  await test("should require re-approval to start time lock of transaction that reached a lowered threshold", async () => {
+    const multisig = await dsl.createMultisigWithTimeLock(2, 3, 60, 1_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const changeThreshold = dsl.createChangeThresholdInstruction(multisig, 1);
+    const [changeThresholdAddress, _txMeta2] = await dsl.proposeTransaction(ownerA, [changeThreshold], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, changeThresholdAddress);
+    await dsl.advanceClock(60);
+    await dsl.executeTransaction(changeThresholdAddress, changeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    // the first transaction now meets the threshold, but its time lock has never been started
+    await dsl.advanceClock(60);
+    let txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x14");
+
+    await dsl.approveTransaction(ownerA, multisig.address, txAddress);
+    await dsl.advanceClock(60);
+    txResult = await dsl.executeTransaction(txAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.isNull(txResult.result);
+  });
+
+  await test("should not allow to change time lock without proposing a transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const changeTimeLock = dsl.createChangeTimeLockInstruction(multisig, 0);
+
+    try {
+      await dsl.createAndProcessTx([changeTimeLock], dsl.programTestContext.payer);
+      fail("Should have failed to execute transaction");
+    } catch (e) {
+      assert(e.message.startsWith("Signature verification failed."));
+    }
+  });
+});
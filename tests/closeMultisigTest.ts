@@ -0,0 +1,102 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {createCloseMultisigInstruction, createCreateMultisigInstruction, findSpendingLimitAddress, MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("close multisig", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should close the multisig and transfer the remaining lamports to the recipient", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const multisigLamports = (await context.banksClient.getAccount(multisig.address)).lamports;
+    const closeMultisig = dsl.createCloseMultisigInstruction(multisig, recipient);
+
+    const txMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [closeMultisig], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    const tombstone = await context.banksClient.getAccount(multisig.address);
+    assert.deepStrictEqual(Array.from(tombstone.data), [6, 1]);  // the closed discriminator and version
+    assert.strictEqual(tombstone.owner.toBase58(), programId.toBase58());
+    await dsl.assertBalance(multisig.signer, 0);
+    await dsl.assertBalance(recipient, 10_000_000 + multisigLamports - tombstone.lamports);
+  });
+
+  await test("should not close the multisig while other transactions are pending", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+    const [_transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+    const closeMultisig = dsl.createCloseMultisigInstruction(multisig, Keypair.generate().publicKey);
+
+    const txMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [closeMultisig], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 1: custom program error: 0x28");
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: PendingTransactions (The multisig cannot be closed while it has pending transactions.)")));
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["pending_transactions"], 1);
+  });
+
+  await test("should close the multisig once its pending transactions are cancelled", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+    await dsl.cancelTransaction(transactionAddress, multisig.address, ownerA, context.payer.publicKey);
+    const closeMultisig = dsl.createCloseMultisigInstruction(multisig, Keypair.generate().publicKey);
+
+    const txMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [closeMultisig], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    assert.deepStrictEqual(Array.from((await context.banksClient.getAccount(multisig.address)).data), [6, 1]);
+  });
+
+  await test("should only close the multisig once its spending limits are removed", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, ownerB] = multisig.owners;
+    const addSpendingLimit = dsl.createAddSpendingLimitInstruction(multisig, 0, PublicKey.default, 1_000_000, 86_400, [ownerB.publicKey]);
+    await dsl.proposeSignAndExecuteTransaction(ownerA, [], [addSpendingLimit], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    const spendingLimit = findSpendingLimitAddress(multisig.address, 0, PublicKey.default, programId);
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["spending_limits"], 1);
+
+    const closeMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [dsl.createCloseMultisigInstruction(multisig, Keypair.generate().publicKey)],
+        multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(closeMeta.result, "Error processing Instruction 1: custom program error: 0x2f");
+    assert(closeMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: ActiveSpendingLimits (The multisig still has spending limits, which have to be removed before it can be closed.)")));
+
+    const removeSpendingLimit = dsl.createRemoveSpendingLimitInstruction(multisig, spendingLimit, multisig.signer);
+    const closeMultisig = dsl.createCloseMultisigInstruction(multisig, Keypair.generate().publicKey);
+    const txMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [removeSpendingLimit, closeMultisig],
+        multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    assert.strictEqual(await context.banksClient.getAccount(spendingLimit), null);
+    assert.deepStrictEqual(Array.from((await context.banksClient.getAccount(multisig.address)).data), [6, 1]);
+  });
+
+  await test("should not re-create a multisig at the address of a closed one", async () => {
+    const multisigKeypair = Keypair.generate();
+    const owners = [Keypair.generate(), Keypair.generate()];
+    const [multisigSigner, nonce] = PublicKey.findProgramAddressSync([multisigKeypair.publicKey.toBuffer()], programId);
+    const payer = context.payer;
+    const createMultisig = createCreateMultisigInstruction(
+        programId, 1, owners, nonce, multisigKeypair.publicKey, multisigSigner, payer.publicKey);
+    await dsl.createAndProcessTx([createMultisig], payer, [multisigKeypair]);
+    const closeMultisig = createCloseMultisigInstruction(multisigSigner, multisigKeypair.publicKey, Keypair.generate().publicKey, programId);
+    const closeMeta = await dsl.proposeSignAndExecuteTransaction(owners[0], [], [closeMultisig],
+        multisigSigner, multisigKeypair.publicKey, owners[0], payer.publicKey);
+    assert.isNull(closeMeta.result);
+
+    const attacker = Keypair.generate();
+    const recreateMultisig = createCreateMultisigInstruction(
+        programId, 1, [attacker], nonce, multisigKeypair.publicKey, multisigSigner, payer.publicKey);
+    const txMeta = await dsl.createAndProcessTx([recreateMultisig], payer, [multisigKeypair]);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
+    assert(txMeta.meta.logMessages.some(log => log.endsWith("Create Account: account Address { address: " + multisigKeypair.publicKey.toBase58() + ", base: None } already in use")));
+  });
+});
@@ -0,0 +1,174 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("close stale transaction", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should let anyone close an expired transaction and refund the rent payer", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const rentPayer = Keypair.generate();
+    await dsl.fund(rentPayer.publicKey, 3_368_640);
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const clock = await context.banksClient.getClock();
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address,
+        {expiry: {UnixTimestamp: clock.unixTimestamp + 60n}, rentPayer});
+    await dsl.advanceClock(61);
+
+    await dsl.assertBalance(rentPayer.publicKey, 0);
+    const txMeta = await dsl.closeStaleTransaction(transactionAddress, multisig.address, rentPayer.publicKey);
+
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(rentPayer.publicKey, 3_368_640); // this is the rent exemption amount
+    const transactionAccountInfo = await context.banksClient.getAccount(transactionAddress, "confirmed");
+    assert.strictEqual(transactionAccountInfo, null);
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["pending_transactions"], 0);
+  });
+
+  await test("should let anyone close a transaction proposed for an outdated owner set", async () => {
+    const multisig = await dsl.createMultisig(1, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    const setOwners = dsl.createSetOwnersInstruction(multisig, multisig.owners.map(owner => owner.publicKey));
+    await dsl.proposeSignAndExecuteTransaction(ownerA, [], [setOwners], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    const txMeta = await dsl.closeStaleTransaction(transactionAddress, multisig.address, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    const transactionAccountInfo = await context.banksClient.getAccount(transactionAddress, "confirmed");
+    assert.strictEqual(transactionAccountInfo, null);
+  });
+
+  await test("should not close a transaction that has not expired yet", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const slot = await context.banksClient.getSlot();
+    const [transactionAddress, _txMeta] = await dsl.proposeTransactionWithExpiry(
+        ownerA, [transactionInstruction], multisig.address, {Slot: slot + 10n});
+
+    const txMeta = await dsl.closeStaleTransaction(transactionAddress, multisig.address, context.payer.publicKey);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: TransactionNotStale (The transaction can still be executed and must be cancelled by an owner instead.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x16");
+    const transactionAccountInfo = await context.banksClient.getAccount(transactionAddress, "confirmed");
+    assert.notEqual(transactionAccountInfo, null);
+  });
+
+  await test("should not close a transaction without expiry", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const txMeta = await dsl.closeStaleTransaction(transactionAddress, multisig.address, context.payer.publicKey);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x16");
+  });
+
+  await test("should only refund the rent payer of the transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const slot = await context.banksClient.getSlot();
+    const [transactionAddress, _txMeta] = await dsl.proposeTransactionWithExpiry(
+        ownerA, [transactionInstruction], multisig.address, {Slot: slot});
+    context.warpToSlot(slot + 1n);
+
+    const txMeta = await dsl.closeStaleTransaction(transactionAddress, multisig.address, ownerA.publicKey);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: RentPayerMismatch (The given account does not match the rent payer of the transaction.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x2c");
+  });
+
+  await test("should not approve or execute an expired transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 1_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const slot = await context.banksClient.getSlot();
+    const [transactionAddress, _txMeta] = await dsl.proposeTransactionWithExpiry(
+        ownerA, [transactionInstruction], multisig.address, {Slot: slot + 1n});
+    context.warpToSlot(slot + 2n);
+
+    const approveMeta = await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    assert.ok(approveMeta.meta.logMessages.includes("Program log: assertion failed - program error: TransactionExpired (The transaction has expired.)"));
+    assert.strictEqual(approveMeta.result, "Error processing Instruction 0: custom program error: 0x15");
+
+    const executeMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.strictEqual(executeMeta.result, "Error processing Instruction 0: custom program error: 0x15");
+    await dsl.assertBalance(multisig.signer, 1_000_000);
+  });
+
+  await test("should execute an approved transaction before its expiry", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 1_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    });
+    const clock = await context.banksClient.getClock();
+    const [transactionAddress, _txMeta] = await dsl.proposeTransactionWithExpiry(
+        ownerA, [transactionInstruction], multisig.address, {UnixTimestamp: clock.unixTimestamp + 60n});
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    await dsl.advanceClock(60);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
+  await test("should not propose an already expired transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const clock = await context.banksClient.getClock();
+    const [_transactionAddress, txMeta] = await dsl.proposeTransactionWithExpiry(
+        ownerA, [transactionInstruction], multisig.address, {UnixTimestamp: clock.unixTimestamp - 1n});
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x15");
+  });
+});
@@ -0,0 +1,113 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {MultisigDsl, toOwners} from "../ts";
+import {assert} from "chai";
+import {createHash} from "node:crypto";
+
+describe("config transactions", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should apply the actions of an approved config transaction to the multisig", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+    const ownerD = Keypair.generate();
+
+    const [transactionAddress, proposeMeta] = await dsl.proposeConfigTransaction(ownerA, [
+      { RemoveOwner: ownerC.publicKey.toBuffer() },
+      { AddOwner: toOwners([ownerD.publicKey])[0] },
+      { ChangeThreshold: 3 },
+      { ChangeTimeLock: 60 },
+    ], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    const executeMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, [], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(proposeMeta.result);
+    assert.isNull(executeMeta.result);
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.deepStrictEqual(actualMultisig["owners"].map(owner => owner["pubkey"]),
+        [ownerA, ownerB, ownerD].map(owner => Array.from(owner.publicKey.toBytes())));
+    assert.strictEqual(actualMultisig["owner_set_seqno"], 1);
+    assert.strictEqual(actualMultisig["threshold"], 3);
+    assert.strictEqual(actualMultisig["time_lock_seconds"], 60);
+  });
+
+  await test("should store the actions as a typed change for approvers to review", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const [transactionAddress, _proposeMeta] = await dsl.proposeConfigTransaction(ownerA, [{ ChangeThreshold: 3 }], multisig.address);
+
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.deepStrictEqual(transaction["config_actions"], [{ ChangeThreshold: 3 }]);
+    assert.deepStrictEqual(transaction["instructions"], []);
+  });
+
+  await test("should record the memo and description hash of a config transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const descriptionHash = createHash("sha256").update("Raise the threshold after the audit").digest();
+
+    const [transactionAddress, proposeMeta] = await dsl.proposeConfigTransaction(ownerA, [{ ChangeThreshold: 3 }], multisig.address,
+        {memo: "raise threshold", descriptionHash});
+
+    assert.isNull(proposeMeta.result);
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.strictEqual(transaction["memo"], "raise threshold");
+    assert.deepStrictEqual(transaction["description_hash"], Array.from(descriptionHash));
+  });
+
+  await test("should reject removing an account that is not an owner at propose time", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const [_transactionAddress, proposeMeta] = await dsl.proposeConfigTransaction(ownerA,
+        [{ RemoveOwner: Keypair.generate().publicKey.toBuffer() }], multisig.address);
+
+    assert.strictEqual(proposeMeta.result, "Error processing Instruction 0: custom program error: 0x2b");
+  });
+
+  await test("should reject a config transaction without actions", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const [_transactionAddress, proposeMeta] = await dsl.proposeConfigTransaction(ownerA, [], multisig.address);
+
+    assert.strictEqual(proposeMeta.result, "Error processing Instruction 0: custom program error: 0x2b");
+  });
+
+  await test("should reject a threshold the resulting owners cannot reach at propose time", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, ownerC] = multisig.owners;
+
+    const [_transactionAddress, proposeMeta] = await dsl.proposeConfigTransaction(ownerA,
+        [{ RemoveOwner: ownerC.publicKey.toBuffer() }, { ChangeThreshold: 3 }], multisig.address);
+
+    assert.strictEqual(proposeMeta.result, "Error processing Instruction 0: custom program error: 0x0");
+  });
+
+  await test("should reject adding an existing owner at propose time", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+
+    const [_transactionAddress, proposeMeta] = await dsl.proposeConfigTransaction(ownerA, [
+      { RemoveOwner: ownerC.publicKey.toBuffer() },
+      { AddOwner: toOwners([ownerB.publicKey])[0] },
+    ], multisig.address);
+
+    assert.strictEqual(proposeMeta.result, "Error processing Instruction 0: custom program error: 0x1");
+  });
+
+  await test("should reject growing the owners beyond the capacity of the multisig account", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const [_transactionAddress, proposeMeta] = await dsl.proposeConfigTransaction(ownerA,
+        [{ AddOwner: toOwners([Keypair.generate().publicKey])[0] }], multisig.address);
+
+    assert.strictEqual(proposeMeta.result, "Error processing Instruction 0: custom program error: 0x7");
+  });
+});
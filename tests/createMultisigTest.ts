@@ -24,7 +24,8 @@ describe("create multisig", async () => {
     const actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(actualMultisig["nonce"], multisig.nonce);
     assert.strictEqual(actualMultisig["threshold"], multisig.threshold);
-    assert.deepEqual(actualMultisig["owners"], multisig.owners.map(owner => Array.from(owner.publicKey.toBytes())));
+    assert.strictEqual(actualMultisig["cancel_threshold"], 1);
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), multisig.owners.map(owner => Array.from(owner.publicKey.toBytes())));
     assert.strictEqual(actualMultisig["owner_set_seqno"], 0);
   });
 
@@ -38,40 +39,40 @@ describe("create multisig", async () => {
 
     assert.strictEqual(actualMultisig1["nonce"], multisig1.nonce);
     assert.strictEqual(actualMultisig1["threshold"], multisig1.threshold);
-    assert.deepStrictEqual(actualMultisig1["owners"], multisig1.owners.map(owner => Array.from(owner.publicKey.toBytes())));
+    assert.deepStrictEqual(actualMultisig1["owners"].map(owner => owner["pubkey"]), multisig1.owners.map(owner => Array.from(owner.publicKey.toBytes())));
     assert.strictEqual(actualMultisig1["owner_set_seqno"], 0);
 
     assert.strictEqual(actualMultisig2["nonce"], multisig2.nonce);
     assert.strictEqual(actualMultisig2["threshold"], multisig2.threshold);
-    assert.deepStrictEqual(actualMultisig2["owners"], multisig2.owners.map(owner => Array.from(owner.publicKey.toBytes())));
+    assert.deepStrictEqual(actualMultisig2["owners"].map(owner => owner["pubkey"]), multisig2.owners.map(owner => Array.from(owner.publicKey.toBytes())));
     assert.strictEqual(actualMultisig2["owner_set_seqno"], 0);
   })
 
   await test("do not create multisig if provided threshold is greater than number of owners", async () => {
     let txMeta = (await dsl.createMultisig(4, 3)).txMeta;
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
-    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the number of owners and greater than zero.)")));
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the total weight of the voting owners and greater than zero.)")));
     assert(txMeta.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x0")));
   });
 
   await test("do not create multisig with 0 threshold", async () => {
     let txMeta = (await dsl.createMultisig(0, 3)).txMeta;
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
-    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the number of owners and greater than zero.)")));
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the total weight of the voting owners and greater than zero.)")));
     assert(txMeta.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x0")));
   });
 
   await test("do not create multisig with 0 threshold and no owners", async () => {
     let txMeta = (await dsl.createMultisigWithOwners(0, [])).txMeta;
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
-    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the number of owners and greater than zero.)")));
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the total weight of the voting owners and greater than zero.)")));
     assert(txMeta.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x0")));
   });
 
   await test("do not create multisig with empty owners list", async () => {
     let txMeta = (await dsl.createMultisigWithOwners(1, [])).txMeta;
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
-    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the number of owners and greater than zero.)")));
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the total weight of the voting owners and greater than zero.)")));
     assert(txMeta.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x0")));
   });
 
@@ -94,6 +95,17 @@ describe("create multisig", async () => {
     assert(txMeta.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x0")));
   });
 
+  await test("do not create multisig with cancel threshold greater than number of owners", async () => {
+    let txMeta = (await dsl.createMultisigWithCancelThreshold(2, 3, 4)).txMeta;
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x18");
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidCancelThreshold (Cancel threshold must be less than or equal to the number of voting owners and greater than zero.)")));
+  });
+
+  await test("do not create multisig with 0 cancel threshold", async () => {
+    let txMeta = (await dsl.createMultisigWithCancelThreshold(2, 3, 0)).txMeta;
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x18");
+  });
+
   await test("do not create multisig with duplicate owners", async () => {
     const [ownerA, ownerB] = Array.from({length: 2}, (_, _n) => Keypair.generate());
     let txMeta = (await dsl.createMultisigWithOwners(2, [ownerA, ownerA, ownerB])).txMeta;
@@ -0,0 +1,60 @@
+import {describe, test} from "node:test";
+import {PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {MultisigAccount, MultisigDsl} from "../ts";
+import {findEphemeralSignerAddress} from "../ts/state/transaction";
+import {assert} from "chai";
+import {createInitializeMint2Instruction, MINT_SIZE, MintLayout, TOKEN_PROGRAM_ID} from "@solana/spl-token";
+
+describe("ephemeral signers", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  async function createMintInstructions(multisig: MultisigAccount, mint: PublicKey) {
+    const rent = await context.banksClient.getRent();
+    return [
+      SystemProgram.createAccount({
+        fromPubkey: multisig.signer,
+        newAccountPubkey: mint,
+        space: MINT_SIZE,
+        lamports: Number(rent.minimumBalance(BigInt(MINT_SIZE))),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(mint, 6, multisig.signer, null, TOKEN_PROGRAM_ID),
+    ];
+  }
+
+  await test("should create an account at an ephemeral signer of the transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 10_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const mint = findEphemeralSignerAddress(await dsl.nextTransactionAddress(multisig.address), 0, programId);
+    const instructions = await createMintInstructions(multisig, mint);
+
+    const [transactionAddress, proposeMeta] = await dsl.proposeTransaction(
+        ownerA, instructions, multisig.address, {ephemeralSigners: 1});
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    const executeMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, instructions, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(proposeMeta.result);
+    assert.isNull(executeMeta.result);
+    const mintAccount = await context.banksClient.getAccount(mint);
+    assert.strictEqual(mintAccount.owner.toBase58(), TOKEN_PROGRAM_ID.toBase58());
+    assert.strictEqual(MintLayout.decode(mintAccount.data).mintAuthority.toBase58(), multisig.signer.toBase58());
+  });
+
+  await test("should not sign for ephemeral signers beyond the count of the transaction", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const mint = findEphemeralSignerAddress(await dsl.nextTransactionAddress(multisig.address), 0, programId);
+    const instructions = await createMintInstructions(multisig, mint);
+
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, instructions, multisig.address);
+    const executeMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, instructions, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(executeMeta.result, "Error processing Instruction 0: Cross-program invocation with unauthorized signer or writable account");
+    assert.strictEqual(await context.banksClient.getAccount(mint), null);
+  });
+});
@@ -0,0 +1,108 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {findSpendingLimitAddress, MultisigDsl, parseMultisigEvents} from "../ts";
+import {assert} from "chai";
+
+describe("events", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should emit an event for each step of the transaction lifecycle", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 10_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+
+    const [transactionAddress, proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+    const approveMeta = await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    const executeMeta = await dsl.executeTransaction(transactionAddress, transfer, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    const [proposed] = parseMultisigEvents(proposeMeta.meta.logMessages);
+    assert.deepStrictEqual(proposed, {TransactionProposed: {
+      multisig: Array.from(multisig.address.toBytes()),
+      transaction: Array.from(transactionAddress.toBytes()),
+      proposer: Array.from(ownerA.publicKey.toBytes()),
+      transaction_index: 1n,
+      vault_index: 0,
+      approval_weight: 1,
+      threshold_reached_at: 0n,
+    }});
+    const [approved] = parseMultisigEvents(approveMeta.meta.logMessages);
+    assert.deepStrictEqual(approved["TransactionApproved"].approver, Array.from(ownerB.publicKey.toBytes()));
+    assert.strictEqual(approved["TransactionApproved"].approval_weight, 2);
+    assert.notStrictEqual(approved["TransactionApproved"].threshold_reached_at, 0n);
+    assert.deepStrictEqual(parseMultisigEvents(executeMeta.meta.logMessages), [{TransactionExecuted: {
+      multisig: Array.from(multisig.address.toBytes()),
+      transaction: Array.from(transactionAddress.toBytes()),
+      executor: Array.from(ownerB.publicKey.toBytes()),
+    }}]);
+  });
+
+  await test("should emit the changes executed by a transaction before the execution itself", async () => {
+    const multisig = await dsl.createMultisig(1, 2);
+    const [ownerA, _ownerB] = multisig.owners;
+    const changeThreshold = dsl.createChangeThresholdInstruction(multisig, 2);
+
+    const txMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [changeThreshold], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    const events = parseMultisigEvents(txMeta.meta.logMessages);
+    assert.deepStrictEqual(events.map(event => Object.keys(event)[0]), ["TransactionProposed", "ThresholdChanged", "TransactionExecuted"]);
+    assert.deepStrictEqual(events[1], {ThresholdChanged: {multisig: Array.from(multisig.address.toBytes()), threshold: 2}});
+  });
+
+  await test("should emit an event when the proposer cancels a transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: ownerA.publicKey});
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
+
+    const cancelMeta = await dsl.cancelTransaction(transactionAddress, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.deepStrictEqual(parseMultisigEvents(cancelMeta.meta.logMessages), [{TransactionCancelled: {
+      multisig: Array.from(multisig.address.toBytes()),
+      transaction: Array.from(transactionAddress.toBytes()),
+      canceller: Array.from(ownerA.publicKey.toBytes()),
+    }}]);
+  });
+
+  await test("should emit events for the lifecycle of spending limits and the closing of the multisig", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, ownerB] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const spendingLimit = findSpendingLimitAddress(multisig.address, 0, PublicKey.default, programId);
+    const addSpendingLimit = dsl.createAddSpendingLimitInstruction(multisig, 0, PublicKey.default, 1_000_000, 86_400, [ownerB.publicKey]);
+
+    const addMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [addSpendingLimit], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    const useMeta = await dsl.useSpendingLimit(ownerB, multisig, 0, recipient, 400_000);
+    const removeSpendingLimit = dsl.createRemoveSpendingLimitInstruction(multisig, spendingLimit, ownerA.publicKey);
+    const closeMultisig = dsl.createCloseMultisigInstruction(multisig, ownerA.publicKey);
+    const closeMeta = await dsl.proposeSignAndExecuteTransaction(ownerA, [], [removeSpendingLimit, closeMultisig], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.deepStrictEqual(parseMultisigEvents(addMeta.meta.logMessages)[1], {SpendingLimitAdded: {
+      multisig: Array.from(multisig.address.toBytes()),
+      spending_limit: Array.from(spendingLimit.toBytes()),
+      vault_index: 0,
+      mint: Array.from(PublicKey.default.toBytes()),
+      amount: 1_000_000n,
+      period_seconds: 86_400n,
+      members: [Array.from(ownerB.publicKey.toBytes())],
+    }});
+    assert.deepStrictEqual(parseMultisigEvents(useMeta.meta.logMessages), [{SpendingLimitUsed: {
+      multisig: Array.from(multisig.address.toBytes()),
+      spending_limit: Array.from(spendingLimit.toBytes()),
+      member: Array.from(ownerB.publicKey.toBytes()),
+      destination: Array.from(recipient.toBytes()),
+      amount: 400_000n,
+      remaining_amount: 600_000n,
+    }}]);
+    const closeEvents = parseMultisigEvents(closeMeta.meta.logMessages);
+    assert.deepStrictEqual(closeEvents.map(event => Object.keys(event)[0]),
+        ["TransactionProposed", "SpendingLimitRemoved", "MultisigClosed", "TransactionExecuted"]);
+    assert.deepStrictEqual(closeEvents[2], {MultisigClosed: {
+      multisig: Array.from(multisig.address.toBytes()),
+      recipient: Array.from(ownerA.publicKey.toBytes()),
+    }});
+  });
+});
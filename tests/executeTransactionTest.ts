@@ -1,10 +1,11 @@
 import {describe, test} from "node:test";
 import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
 import {start} from "solana-bankrun";
-import {MultisigDsl, MultisigSchema} from "../ts";
+import {MultisigDsl, MultisigSchema, Permission} from "../ts";
 import {assert} from "chai";
-import {createTransferCheckedInstruction} from "@solana/spl-token";
+import {ACCOUNT_SIZE, createGetAccountDataSizeInstruction, createTransferCheckedInstruction, TOKEN_PROGRAM_ID} from "@solana/spl-token";
 import {Buffer} from "node:buffer";
+import {createHash} from "node:crypto";
 import * as borsh from "borsh";
 import {TransactionSchema} from "../ts/state/transaction";
 
@@ -30,7 +31,7 @@ describe("execute transaction", async () => {
     const [transactionAddress, _proposeTxMeta] = await dsl.proposeTransaction(ownerA, [solTransferInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
-    const txMeta = await dsl.executeTransaction(transactionAddress, solTransferInstruction, multisig.signer, multisig.address, ownerA, ownerA.publicKey);
+    const txMeta = await dsl.executeTransaction(transactionAddress, solTransferInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
 
     assert.isNull(txMeta.result);  // i.e. executeTransaction completed without error
     await dsl.assertBalance(multisig.signer, 1_100_000);
@@ -59,7 +60,7 @@ describe("execute transaction", async () => {
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [tokenTransferInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
-    await dsl.executeTransaction(transactionAddress, tokenTransferInstruction, multisig.signer, multisig.address, ownerA, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress, tokenTransferInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
 
     await dsl.assertTokenAccountBalance(multisigOwnedAta, 5);
     await dsl.assertTokenAccountBalance(destinationAta, 15);
@@ -100,7 +101,7 @@ describe("execute transaction", async () => {
         multisig.signer,
         multisig.address,
         ownerA,
-        ownerA.publicKey
+        context.payer.publicKey
     );
 
     await dsl.assertBalance(multisig.signer, 950_000);
@@ -136,7 +137,7 @@ describe("execute transaction", async () => {
         multisig.signer,
         multisig.address,
         ownerA,
-        ownerA.publicKey);
+        context.payer.publicKey);
 
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x1")
     assert.ok(txResult.meta.logMessages.includes("Transfer: insufficient lamports 400000, need 500000"));
@@ -157,7 +158,7 @@ describe("execute transaction", async () => {
 
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
-    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     await dsl.assertBalance(multisig.signer, 0);
   });
@@ -177,12 +178,12 @@ describe("execute transaction", async () => {
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
 
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
-    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerC, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerC, context.payer.publicKey);
 
     await dsl.assertBalance(multisig.signer, 0);
   });
 
-  await test("close transaction account and refund rent exemption SOL on execute transaction", async () => {
+  await test("shrink transaction account to a receipt and refund the remaining rent on execute transaction", async () => {
     const multisig = await dsl.createMultisig(2, 3, 1_000_000);
     const [ownerA, ownerB, _ownerC] = multisig.owners;
 
@@ -191,18 +192,25 @@ describe("execute transaction", async () => {
       lamports: 1_000_000,
       toPubkey: context.payer.publicKey,
     });
-    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    const rentPayer = Keypair.generate();
+    await dsl.fund(rentPayer.publicKey, 3_368_640);
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address,
+        {rentPayer});
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
-    await dsl.assertBalance(ownerA.publicKey, 0);
-    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
-    await dsl.assertBalance(ownerA.publicKey, 2_053_200);  // this is the rent exemption amount
+    await dsl.assertBalance(rentPayer.publicKey, 0);
+    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, rentPayer.publicKey);
+    await dsl.assertBalance(rentPayer.publicKey, 1_419_840);  // the rent exemption amount of the transaction minus that of its receipt
 
-    let rawTxAccount = await context.banksClient.getAccount(transactionAddress, "confirmed");
-    assert.strictEqual(rawTxAccount, null);
+    const receipt = await dsl.getTransactionReceipt(transactionAddress);
+    assert.strictEqual(receipt["transaction_index"], 1n);
+    assert.deepStrictEqual(receipt["executor"], Array.from(ownerB.publicKey.toBytes()));
+    assert.strictEqual(receipt["receipts"].length, 1);
+    assert.deepStrictEqual(receipt["receipts"][0].return_data_hash, Array(32).fill(0));  // transfers return no data
+    assert.notStrictEqual(receipt["receipts"][0].slot, 0n);
   });
 
-  await test("refund rent exemption SOL to any nominated account", async () => {
+  await test("should not refund rent exemption SOL to any account other than the rent payer", async () => {
     const multisig = await dsl.createMultisig(2, 3, 1_000_000);
     const [ownerA, ownerB, _ownerC] = multisig.owners;
     const otherAccount = Keypair.generate();
@@ -218,8 +226,11 @@ describe("execute transaction", async () => {
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
     await dsl.assertBalance(otherAccount.publicKey, 0);
 
-    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, otherAccount.publicKey);
-    await dsl.assertBalance(otherAccount.publicKey, 2_053_200);  // this is the rent exemption amount
+    const txMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, otherAccount.publicKey);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: RentPayerMismatch (The given account does not match the rent payer of the transaction.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x2c");
+    await dsl.assertBalance(otherAccount.publicKey, 0);
   });
 
   await test("should not clear up transaction account if execute fails", async () => {
@@ -235,7 +246,7 @@ describe("execute transaction", async () => {
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
     try {
-      await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+      await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
       assert.fail("The executeTransaction function should have failed");
     } catch (e) {
       let rawTxAccount = await context.banksClient.getAccount(transactionAddress, "confirmed");
@@ -258,14 +269,15 @@ describe("execute transaction", async () => {
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
-    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     await dsl.assertBalance(multisig.signer, 990_000);
 
-    const txResult = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, ownerA.publicKey);
+    const txResult = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
     await dsl.assertBalance(multisig.signer, 990_000);
 
-    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: AccountOwnedByWrongProgram (The given account is owned by a different program than expected.)"));
-    assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x13");
+    // the transaction account has been replaced by its receipt
+    assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: MalformedTransactionAccount (The given transaction account is missing or not in the expected format.)"));
+    assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0xf");
   });
 
   await test("should not let a non-owner execute transaction", async () => {
@@ -284,7 +296,7 @@ describe("execute transaction", async () => {
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
 
-    const txResult = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerD, ownerA.publicKey);
+    const txResult = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerD, context.payer.publicKey);
 
     assert.ok(txResult.meta.logMessages.includes("Program log: assertion failed - program error: InvalidExecutor (The executor must be a signer and an owner of this multisig.)"))
     assert.strictEqual(txResult.result, "Error processing Instruction 0: custom program error: 0x8");
@@ -310,16 +322,16 @@ describe("execute transaction", async () => {
     await dsl.approveTransaction(ownerB, multisig.address, txAddress1);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress2);
 
-    await dsl.executeTransaction(txAddress1, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
-    await dsl.executeTransaction(txAddress2, transactionInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress1, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+    await dsl.executeTransaction(txAddress2, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     await dsl.assertBalance(multisig.signer, 0);
   });
 
   await test("should transfer funds from two different multisig accounts", async () => {
     const [ownerA, ownerB, ownerC, ownerD] = Array.from({length: 4}, (_, _n) => Keypair.generate());
-    const multisig1 = await dsl.createMultisigWithOwners(2, [ownerA, ownerB, ownerC], 1_000_000);
-    const multisig2 = await dsl.createMultisigWithOwners(2, [ownerB, ownerC, ownerD], 1_100_000);
+    const multisig1 = await dsl.createMultisigWithOwners(2, [ownerA, ownerB, ownerC], {initialBalance: 1_000_000});
+    const multisig2 = await dsl.createMultisigWithOwners(2, [ownerB, ownerC, ownerD], {initialBalance: 1_100_000});
     await dsl.assertBalance(multisig1.signer, 1_000_000);
     await dsl.assertBalance(multisig2.signer, 1_100_000);
 
@@ -340,8 +352,8 @@ describe("execute transaction", async () => {
     await dsl.approveTransaction(ownerB, multisig1.address, txAddress1);
     await dsl.approveTransaction(ownerC, multisig2.address, txAddress2);
 
-    await dsl.executeTransaction(txAddress1, transactionInstruction1, multisig1.signer, multisig1.address, ownerB, ownerA.publicKey);
-    await dsl.executeTransaction(txAddress2, transactionInstruction2, multisig2.signer, multisig2.address, ownerC, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress1, transactionInstruction1, multisig1.signer, multisig1.address, ownerB, context.payer.publicKey);
+    await dsl.executeTransaction(txAddress2, transactionInstruction2, multisig2.signer, multisig2.address, ownerC, context.payer.publicKey);
 
     await dsl.assertBalance(multisig1.signer, 950_000);
     await dsl.assertBalance(multisig2.signer, 1_000_000);
@@ -375,6 +387,66 @@ describe("execute transaction", async () => {
     assert.notEqual(transactionAccountInfo, null, "Transaction account should not have been closed on error.");
   });
 
+  await test("should record the return data of each instruction on the receipt", async () => {
+    const multisig = await dsl.createMultisig(1, 2);
+    const [ownerA, _ownerB] = multisig.owners;
+    const mint = await dsl.createTokenMint(3);
+    const getAccountDataSize = createGetAccountDataSizeInstruction(mint.account);
+    const [transactionAddress, _proposeTxMeta] = await dsl.proposeTransaction(ownerA, [getAccountDataSize], multisig.address);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, getAccountDataSize, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    const returnData = Buffer.alloc(8);
+    returnData.writeBigUInt64LE(BigInt(ACCOUNT_SIZE));
+    const expectedHash = createHash("sha256").update(TOKEN_PROGRAM_ID.toBuffer()).update(returnData).digest();
+    const receipt = await dsl.getTransactionReceipt(transactionAddress);
+    assert.deepStrictEqual(receipt["receipts"][0].return_data_hash, Array.from(expectedHash));
+  });
+
+  await test("should execute a transaction step-wise, keeping it until the last instruction is executed", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 10_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const recipients = Array.from({length: 3}, () => Keypair.generate().publicKey);
+    const transfers = recipients.map(recipient =>
+        SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: recipient}));
+    const [transactionAddress, _proposeTxMeta] = await dsl.proposeTransaction(ownerA, transfers, multisig.address);
+
+    const firstStepMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, transfers.slice(0, 2), multisig.signer, multisig.address, ownerA, context.payer.publicKey, [], 2);
+
+    assert.isNull(firstStepMeta.result);
+    assert.strictEqual((await dsl.getTransactionAccount(transactionAddress))["executed_up_to"], 2);
+    await dsl.assertBalance(recipients[1], 1_000_000);
+    await dsl.assertBalance(recipients[2], 0);
+
+    const lastStepMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, transfers.slice(2), multisig.signer, multisig.address, ownerA, context.payer.publicKey, [], 2);
+
+    assert.isNull(lastStepMeta.result);
+    await dsl.assertBalance(recipients[2], 1_000_000);
+    assert.strictEqual((await dsl.getTransactionReceipt(transactionAddress))["receipts"].length, 3);
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["pending_transactions"], 0);
+  });
+
+  await test("should complete a step-wise execution after an executed instruction changed the owners", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+    const setOwners = dsl.createSetOwnersInstruction(multisig, [ownerA.publicKey, ownerB.publicKey]);
+    const transfer = SystemProgram.transfer({fromPubkey: multisig.signer, lamports: 1_000_000, toPubkey: recipient});
+    const [transactionAddress, _proposeTxMeta] = await dsl.proposeTransaction(ownerA, [setOwners, transfer], multisig.address);
+
+    await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, [setOwners], multisig.signer, multisig.address, ownerA, context.payer.publicKey, [], 1);
+    const txMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, [transfer], multisig.signer, multisig.address, ownerA, context.payer.publicKey, [], 1);
+
+    assert.isNull(txMeta.result);
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["owners"].length, 2);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
   await test("should reject a Multisig account not owned by the program", async () => {
     const attacker = Keypair.generate();
     const fakeMultisigAddress = Keypair.generate().publicKey;
@@ -385,10 +457,17 @@ describe("execute transaction", async () => {
 
     const fakeMultisigData = Buffer.from(
         borsh.serialize(MultisigSchema, {
-          owners: [attacker.publicKey.toBytes()],
+          discriminator: 1,
+          version: 1,
+          owners: [{pubkey: attacker.publicKey.toBytes(), permissions: Permission.All, weight: 1}],
           threshold: 1,
+          cancel_threshold: 1,
           nonce: nonce,
           owner_set_seqno: 0,
+          time_lock_seconds: 0,
+          transaction_index: 0,
+          pending_transactions: 0,
+          spending_limits: 0,
           padding: [],
         }),
     );
@@ -404,10 +483,32 @@ describe("execute transaction", async () => {
     const fakeTxAddress = Keypair.generate().publicKey;
     const fakeTxData = Buffer.from(
         borsh.serialize(TransactionSchema, {
+          discriminator: 2,
+          version: 1,
           multisig: fakeMultisigAddress.toBytes(),
+          transaction_index: 1,
           instructions: [],
+          config_actions: [],
+          address_lookup_tables: [],
+          vault_index: 0,
+          ephemeral_signers: 0,
+          executed_up_to: 0,
+          receipts: [],
           signers: [true],
+          rejectors: [false],
+          revokers: [false],
+          rejected: false,
+          cancellers: [false],
+          cancellers_owner_set_seqno: 0,
           owner_set_seqno: 0,
+          threshold_reached_at: 0,
+          proposer: PublicKey.default.toBytes(),
+          rent_payer: PublicKey.default.toBytes(),
+          created_at_slot: 0,
+          created_at: 0,
+          memo: null,
+          description_hash: null,
+          expiry: null,
         }),
     );
     context.setAccount(fakeTxAddress, {
@@ -445,10 +546,32 @@ describe("execute transaction", async () => {
     const fakeTxAddress = Keypair.generate().publicKey;
     const fakeTxData = Buffer.from(
         borsh.serialize(TransactionSchema, {
+          discriminator: 2,
+          version: 1,
           multisig: multisig.address.toBytes(),
+          transaction_index: 1,
           instructions: [],
+          config_actions: [],
+          address_lookup_tables: [],
+          vault_index: 0,
+          ephemeral_signers: 0,
+          executed_up_to: 0,
+          receipts: [],
           signers: [true],
+          rejectors: [false],
+          revokers: [false],
+          rejected: false,
+          cancellers: [false],
+          cancellers_owner_set_seqno: 0,
           owner_set_seqno: 0,
+          threshold_reached_at: 0,
+          proposer: PublicKey.default.toBytes(),
+          rent_payer: PublicKey.default.toBytes(),
+          created_at_slot: 0,
+          created_at: 0,
+          memo: null,
+          description_hash: null,
+          expiry: null,
         }),
     );
     context.setAccount(fakeTxAddress, {
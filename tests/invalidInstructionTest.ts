@@ -13,7 +13,9 @@ describe("invalid instruction", async () => {
       instructionDiscriminator: 255,
       owners: [],
       threshold: 100,
-      nonce: 250
+      nonce: 250,
+      time_lock_seconds: 0,
+      cancel_threshold: 1
     });
     const invalidInstruction = new TransactionInstruction({
       keys: [
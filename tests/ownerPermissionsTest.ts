@@ -0,0 +1,111 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {MultisigDsl, Permission} from "../ts";
+import {assert} from "chai";
+
+describe("owner permissions", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should create multisig with owner permissions", async () => {
+    const permissions = [Permission.Propose, Permission.Vote, Permission.Vote | Permission.Execute];
+    const multisig = await dsl.createMultisigWithPermissions(2, permissions);
+
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["permissions"]), permissions);
+  });
+
+  await test("should let a propose-only owner propose without approving", async () => {
+    const multisig = await dsl.createMultisigWithPermissions(1, [Permission.Propose, Permission.All]);
+    const [bot, _ownerB] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, txMeta] = await dsl.proposeTransaction(bot, [transactionInstruction], multisig.address);
+
+    assert.isNull(txMeta.result);
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.deepEqual(transaction["signers"], [false, false]);
+  });
+
+  await test("should not let an owner without propose permission propose", async () => {
+    const multisig = await dsl.createMultisigWithPermissions(1, [Permission.Vote, Permission.All]);
+    const [coldKey, _ownerB] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [_transactionAddress, txMeta] = await dsl.proposeTransaction(coldKey, [transactionInstruction], multisig.address);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: MissingPermission (The owner does not have the permission required for this instruction.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x1a");
+  });
+
+  await test("should not let an owner without vote permission approve", async () => {
+    const multisig = await dsl.createMultisigWithPermissions(1, [Permission.All, Permission.Propose | Permission.Execute]);
+    const [ownerA, keeper] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const txMeta = await dsl.approveTransaction(keeper, multisig.address, transactionAddress);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x1a");
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [true, false]);
+  });
+
+  await test("should only let owners with execute permission execute", async () => {
+    const multisig = await dsl.createMultisigWithPermissions(1, [Permission.Propose | Permission.Vote, Permission.Execute], 1_000_000);
+    const [ownerA, keeper] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const failedMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.strictEqual(failedMeta.result, "Error processing Instruction 0: custom program error: 0x1a");
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, keeper, context.payer.publicKey);
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
+  await test("should not create multisig with threshold above the number of voting owners", async () => {
+    const multisig = await dsl.createMultisigWithPermissions(2, [Permission.All, Permission.Propose | Permission.Execute]);
+
+    assert.strictEqual(multisig.txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
+  });
+
+  await test("should not create multisig without an owner holding each permission", async () => {
+    const multisig = await dsl.createMultisigWithPermissions(1, [Permission.Propose | Permission.Vote, Permission.Vote]);
+
+    assert(multisig.txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidPermissions (Owner permissions must be non-empty and every permission must be held by at least one owner.)")));
+    assert.strictEqual(multisig.txMeta.result, "Error processing Instruction 0: custom program error: 0x19");
+  });
+
+  await test("should not set owners without an owner holding each permission", async () => {
+    const multisig = await dsl.createMultisig(1, 2);
+    const [ownerA, ownerB] = multisig.owners;
+
+    const setOwners = dsl.createSetOwnersInstruction(multisig, [ownerA.publicKey, ownerB.publicKey], [Permission.Propose, Permission.Vote]);
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
+    const txMeta = await dsl.executeTransaction(transactionAddress, setOwners, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x19");
+  });
+});
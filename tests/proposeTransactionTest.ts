@@ -1,11 +1,12 @@
 import {describe, test} from "node:test";
 import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
 import {start} from "solana-bankrun";
-import {MultisigDsl, MultisigSchema} from "../ts";
+import {MultisigDsl, MultisigSchema, Permission} from "../ts";
 import {assert} from "chai";
-import {Transaction} from "../ts/state/transaction";
+import {findTransactionAddress, Transaction} from "../ts/state/transaction";
 import {Buffer} from "node:buffer";
 import * as borsh from "borsh";
+import {createHash} from "node:crypto";
 
 describe("propose transaction", async () => {
   const programId = PublicKey.unique();
@@ -76,7 +77,7 @@ describe("propose transaction", async () => {
     assert(txMeta.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x4")));
   });
 
-  await test("should not be able to edit transaction account with transaction account private key after initialisation", async () => {
+  await test("should derive transaction accounts from the multisig and a sequential index", async () => {
     const multisig = await dsl.createMultisig(2, 3);
     const transactionInstruction = SystemProgram.transfer({
       fromPubkey: multisig.signer,
@@ -84,23 +85,19 @@ describe("propose transaction", async () => {
       toPubkey: context.payer.publicKey,
     });
 
-    const transactionKeypair = Keypair.generate();
-    const [_, txMeta] = await dsl.proposeTransaction(multisig.owners[0], [transactionInstruction], multisig.address, transactionKeypair);
-    assert.isNull(txMeta.result);
-
-    // Try to transfer SOL from the transaction account using its private key
-    const stealInstruction = SystemProgram.transfer({
-      fromPubkey: transactionKeypair.publicKey,
-      lamports: 1,
-      toPubkey: context.payer.publicKey,
-    });
-    const stealResult = await dsl.createAndProcessTx([stealInstruction], context.payer, [transactionKeypair]);
+    const [txAddress1, txMeta1] = await dsl.proposeTransaction(multisig.owners[0], [transactionInstruction], multisig.address);
+    const [txAddress2, txMeta2] = await dsl.proposeTransaction(multisig.owners[1], [transactionInstruction], multisig.address);
 
-    assert.isNotNull(stealResult.result);
-    assert(stealResult.meta.logMessages.some(log => log.includes("`from` must not carry data")));
+    assert.isNull(txMeta1.result);
+    assert.isNull(txMeta2.result);
+    assert.strictEqual(txAddress1.toBase58(), findTransactionAddress(multisig.address, 1n, programId).toBase58());
+    assert.strictEqual(txAddress2.toBase58(), findTransactionAddress(multisig.address, 2n, programId).toBase58());
+    assert.strictEqual((await dsl.getTransactionAccount(txAddress1))["transaction_index"], 1n);
+    assert.strictEqual((await dsl.getTransactionAccount(txAddress2))["transaction_index"], 2n);
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["transaction_index"], 2n);
   });
 
-  await test("should not be able propose 2 transactions to the same transaction address", async () => {
+  await test("should not be able to propose a transaction to an address other than the next transaction index", async () => {
     const multisig = await dsl.createMultisig(2, 3);
 
     const transactionInstruction = SystemProgram.transfer({
@@ -109,20 +106,16 @@ describe("propose transaction", async () => {
       toPubkey: context.payer.publicKey,
     });
 
-    const txKeypair: Keypair = Keypair.generate();
-    const [txAddress1, txMeta1] = await dsl.proposeTransaction(multisig.owners[0], [transactionInstruction], multisig.address, txKeypair);
+    const [txAddress1, txMeta1] = await dsl.proposeTransaction(multisig.owners[0], [transactionInstruction], multisig.address);
     const currentSlot = await context.banksClient.getSlot();
     context.warpToSlot(currentSlot + 1n);  // avoid posting the same TX in the same block
-    const [txAddress2, txMeta2] = await dsl.proposeTransaction(multisig.owners[0], [transactionInstruction], multisig.address, txKeypair);
+    const [_txAddress2, txMeta2] = await dsl.proposeTransaction(multisig.owners[0], [transactionInstruction], multisig.address, {transactionAddress: txAddress1});
+    const [_txAddress3, txMeta3] = await dsl.proposeTransaction(multisig.owners[0], [transactionInstruction], multisig.address, {transactionAddress: Keypair.generate().publicKey});
 
-    assert.strictEqual(txAddress1.toBase58(), txKeypair.publicKey.toBase58());
     assert.isNull(txMeta1.result);
-
-    assert.strictEqual(txAddress2.toBase58(), txKeypair.publicKey.toBase58());
-    assert.strictEqual(txMeta2.result, "Error processing Instruction 0: custom program error: 0x0");
-
-    assert(txMeta2.meta.logMessages.some(log => log.endsWith(" already in use")));
-    assert(txMeta2.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x0")));
+    assert.strictEqual(txMeta2.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
+    assert.strictEqual(txMeta3.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
+    assert.strictEqual((await dsl.getMultisig(multisig.address))["transaction_index"], 1n);
   });
 
   await test("should reject a Multisig account not owned by the program", async () => {
@@ -130,10 +123,17 @@ describe("propose transaction", async () => {
     const fakeMultisigAddress = Keypair.generate().publicKey;
     const fakeMultisigData = Buffer.from(
         borsh.serialize(MultisigSchema, {
-          owners: [attacker.publicKey.toBytes()],
+          discriminator: 1,
+          version: 1,
+          owners: [{pubkey: attacker.publicKey.toBytes(), permissions: Permission.All, weight: 1}],
           threshold: 1,
+          cancel_threshold: 1,
           nonce: 0,
           owner_set_seqno: 0,
+          time_lock_seconds: 0,
+          transaction_index: 0,
+          pending_transactions: 0,
+          spending_limits: 0,
           padding: [],
         }),
     );
@@ -162,4 +162,43 @@ describe("propose transaction", async () => {
         "expected propose_transaction to reject with AccountOwnedByWrongProgram (0x13)",
     );
   });
+
+  await test("should record the proposer, creation time, memo and description hash of the transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [_ownerA, ownerB, _ownerC] = multisig.owners;
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+    const descriptionHash = createHash("sha256").update("Quarterly payment to the auditors").digest();
+    const clock = await context.banksClient.getClock();
+
+    const [transactionAddress, txMeta] = await dsl.proposeTransaction(ownerB, [transactionInstruction], multisig.address,
+        {memo: "Q3 audit fee", descriptionHash});
+
+    assert.isNull(txMeta.result);
+    const transactionAccount = await dsl.getTransactionAccount(transactionAddress);
+    assert.deepStrictEqual(transactionAccount["proposer"], Array.from(ownerB.publicKey.toBytes()));
+    assert.strictEqual(transactionAccount["created_at_slot"], clock.slot);
+    assert.strictEqual(transactionAccount["created_at"], clock.unixTimestamp);
+    assert.strictEqual(transactionAccount["memo"], "Q3 audit fee");
+    assert.deepStrictEqual(transactionAccount["description_hash"], Array.from(descriptionHash));
+  });
+
+  await test("should not propose a transaction with a memo longer than 64 bytes", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: context.payer.publicKey,
+    });
+
+    const [_transactionAddress, txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address,
+        {memo: "x".repeat(65)});
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: MemoTooLong (The memo of a transaction must not exceed 64 bytes.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x2d");
+  });
 });
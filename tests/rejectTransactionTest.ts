@@ -0,0 +1,139 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {createRejectTransactionInstruction, MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("reject transaction", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should record rejection without rejecting transaction while threshold is still reachable", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const txMeta = await dsl.rejectTransaction(ownerB, multisig.address, transactionAddress);
+
+    assert.isNull(txMeta.result);
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.deepEqual(transaction["rejectors"], [false, true, false]);
+    assert.isFalse(transaction["rejected"]);
+  });
+
+  await test("should reject transaction once threshold becomes unreachable", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 1_000_000);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    await dsl.rejectTransaction(ownerB, multisig.address, transactionAddress);
+    await dsl.rejectTransaction(ownerC, multisig.address, transactionAddress);
+
+    assert.isTrue((await dsl.getTransactionAccount(transactionAddress))["rejected"]);
+
+    const approveMeta = await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    assert.ok(approveMeta.meta.logMessages.includes("Program log: assertion failed - program error: TransactionRejected (The transaction has been rejected by the owners.)"));
+    assert.strictEqual(approveMeta.result, "Error processing Instruction 0: custom program error: 0x17");
+
+    const executeMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.strictEqual(executeMeta.result, "Error processing Instruction 0: custom program error: 0x17");
+    await dsl.assertBalance(multisig.signer, 1_000_000);
+  });
+
+  await test("should let anyone close a rejected transaction and refund the rent payer", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const rentPayer = Keypair.generate();
+    await dsl.fund(rentPayer.publicKey, 3_368_640);
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address,
+        {rentPayer});
+    await dsl.rejectTransaction(ownerB, multisig.address, transactionAddress);
+    await dsl.rejectTransaction(ownerC, multisig.address, transactionAddress);
+
+    await dsl.assertBalance(rentPayer.publicKey, 0);
+    const txMeta = await dsl.closeStaleTransaction(transactionAddress, multisig.address, rentPayer.publicKey);
+
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(rentPayer.publicKey, 3_368_640); // this is the rent exemption amount
+    const transactionAccountInfo = await context.banksClient.getAccount(transactionAddress, "confirmed");
+    assert.strictEqual(transactionAccountInfo, null);
+  });
+
+  await test("should withdraw the approval of a rejecting owner", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+
+    await dsl.rejectTransaction(ownerB, multisig.address, transactionAddress);
+
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.deepEqual(transaction["signers"], [true, false, false]);
+    assert.deepEqual(transaction["rejectors"], [false, true, false]);
+
+    const executeMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.strictEqual(executeMeta.result, "Error processing Instruction 0: custom program error: 0xd");
+  });
+
+  await test("should not let a non-owner reject a transaction", async () => {
+    const multisig = await dsl.createMultisig(1, 2);
+    const [ownerA, _ownerB] = multisig.owners;
+    const notAnOwner = Keypair.generate();
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const txMeta = await dsl.rejectTransaction(notAnOwner, multisig.address, transactionAddress);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: InvalidOwner (The given owner is not part of this multisig.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x2");
+  });
+
+  await test("should not let an owner reject a transaction without signing", async () => {
+    const multisig = await dsl.createMultisig(1, 2);
+    const [ownerA, ownerB] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const reject = createRejectTransactionInstruction(multisig.address, transactionAddress, ownerB.publicKey, programId);
+    reject.keys[2].isSigner = false;
+    const txMeta = await dsl.createAndProcessTx([reject], context.payer);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: OwnerNotSigner (The owner must be a signer.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x31");
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["rejectors"], [false, false]);
+  });
+});
@@ -0,0 +1,111 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {createRevokeApprovalInstruction, MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("revoke approval", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should let owner revoke their approval", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 1_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [true, true, false]);
+
+    const txMeta = await dsl.revokeApproval(ownerB, multisig.address, transactionAddress);
+
+    assert.isNull(txMeta.result);
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [true, false, false]);
+    const executeMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    assert.strictEqual(executeMeta.result, "Error processing Instruction 0: custom program error: 0xd");
+    await dsl.assertBalance(multisig.signer, 1_000_000);
+  });
+
+  await test("should keep the approvals of other owners", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 1_000_000);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    await dsl.revokeApproval(ownerA, multisig.address, transactionAddress);
+    await dsl.approveTransaction(ownerC, multisig.address, transactionAddress);
+
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [false, true, true]);
+    const executeMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+    assert.isNull(executeMeta.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
+  await test("should restart the time lock when dropping below the threshold", async () => {
+    const multisig = await dsl.createMultisigWithTimeLock(2, 3, 60);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    assert.notEqual((await dsl.getTransactionAccount(transactionAddress))["threshold_reached_at"], 0n);
+
+    await dsl.revokeApproval(ownerB, multisig.address, transactionAddress);
+
+    assert.strictEqual((await dsl.getTransactionAccount(transactionAddress))["threshold_reached_at"], 0n);
+  });
+
+  await test("should not let a non-owner revoke an approval", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const notAnOwner = Keypair.generate();
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const txMeta = await dsl.revokeApproval(notAnOwner, multisig.address, transactionAddress);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: InvalidOwner (The given owner is not part of this multisig.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x2");
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [true, false, false]);
+  });
+
+  await test("should not let an owner revoke an approval without signing", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [transactionInstruction], multisig.address);
+
+    const revoke = createRevokeApprovalInstruction(multisig.address, transactionAddress, ownerA.publicKey, programId);
+    revoke.keys[2].isSigner = false;
+    const txMeta = await dsl.createAndProcessTx([revoke], context.payer);
+
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: OwnerNotSigner (The owner must be a signer.)"));
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x31");
+    assert.deepEqual((await dsl.getTransactionAccount(transactionAddress))["signers"], [true, false, false]);
+  });
+});
@@ -19,13 +19,13 @@ describe("set owners", async () => {
     const setOwnersAndChangeThreshold = dsl.createSetOwnersAndChangeThresholdInstruction(multisig, newOwners, 1);
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
-    await dsl.executeTransaction(transactionAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
 
     const actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(actualMultisig["nonce"], multisig.nonce);
     assert.strictEqual(actualMultisig["threshold"], 1);
-    assert.deepEqual(actualMultisig["owners"], newOwners.map(owner => Array.from(owner.toBytes())));
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), newOwners.map(owner => Array.from(owner.toBytes())));
     assert.strictEqual(actualMultisig["owner_set_seqno"], 1);
   });
 
@@ -37,7 +37,7 @@ describe("set owners", async () => {
     const setOwnersAndChangeThreshold = dsl.createSetOwnersAndChangeThresholdInstruction(multisig, [newOwnerA.publicKey, newOwnerB.publicKey, newOwnerC.publicKey], 1);
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
-    await dsl.executeTransaction(transactionAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
 
     const setOwners = dsl.createSetOwnersInstruction(multisig, multisig.owners.map(owner => owner.publicKey));
@@ -56,7 +56,7 @@ describe("set owners", async () => {
     const setOwnersAndChangeThreshold = dsl.createSetOwnersAndChangeThresholdInstruction(multisig, [newOwnerA.publicKey, newOwnerB.publicKey, newOwnerC.publicKey], 1);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const setOwners2 = dsl.createSetOwnersInstruction(multisig, multisig.owners.map(owner => owner.publicKey));
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(newOwnerA, [setOwners2], multisig.address);
@@ -83,7 +83,7 @@ describe("set owners", async () => {
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
 
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const transactionAccount = await dsl.getTransactionAccount(txAddress2);
     const actualMultisig = await dsl.getMultisig(multisig.address);
@@ -115,14 +115,14 @@ describe("set owners", async () => {
 
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress2);
-    await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     let txAccount = await dsl.getTransactionAccount(txAddress2);
     let actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(txAccount["owner_set_seqno"], 0, "Owner set sequence number should not have updated");
     assert.strictEqual(actualMultisig["owner_set_seqno"], 1, "Should have incremented owner set seq number");
 
-    const txMeta = await dsl.executeTransaction(txAddress2, transfer, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txMeta = await dsl.executeTransaction(txAddress2, transfer, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x8");
     assert(txMeta.meta.logMessages.some(log => log.endsWith(
         " assertion failed - program error: InvalidExecutor (The executor must be a signer and an owner of this multisig.)"
@@ -154,7 +154,7 @@ describe("set owners", async () => {
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
 
-    const txMeta = await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txMeta = await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x6");
     assert(txMeta.meta.logMessages.some(log => log.endsWith(
@@ -172,10 +172,10 @@ describe("set owners", async () => {
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThreshold], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
 
-    const txMeta = await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txMeta = await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
-    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the number of owners and greater than zero.)"))
+    assert.ok(txMeta.meta.logMessages.includes("Program log: assertion failed - program error: InvalidThreshold (Threshold must be less than or equal to the total weight of the voting owners and greater than zero.)"))
   });
 
   await test("should not allow to set owners and change threshold without proposing a transaction", async () => {
@@ -191,6 +191,23 @@ describe("set owners", async () => {
     }
   });
 
+  await test("should grow the multisig account when increasing the number of owners and threshold with a payer", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 10_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const newOwners = Array.from({length: 4}, () => Keypair.generate().publicKey);
+
+    const setOwnersAndChangeThreshold = dsl.createSetOwnersAndChangeThresholdInstruction(multisig, newOwners, 3, multisig.signer);
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThreshold], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+    const txMeta = await dsl.executeTransaction(txAddress, setOwnersAndChangeThreshold, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.strictEqual(actualMultisig["owners"].length, 4);
+    assert.strictEqual(actualMultisig["threshold"], 3);
+    assert.deepEqual(actualMultisig["padding"], []);
+  });
+
   await test("should not allow owners and threshold to be changed without passing in correct multisig signer", async () => {
     const multisig = await dsl.createMultisig(2, 3);
     const [ownerA, ownerB, ownerC] = multisig.owners;
@@ -201,7 +218,7 @@ describe("set owners", async () => {
     );
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThresholdUsingPayer], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    let txResult = await dsl.executeTransaction(txAddress, setOwnersAndChangeThresholdUsingPayer, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    let txResult = await dsl.executeTransaction(txAddress, setOwnersAndChangeThresholdUsingPayer, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     assert.strictEqual(txResult.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
 
     const setOwnersAndChangeThresholdUsingOwner = dsl.createSetOwnersAndChangeThresholdInstructionManualSigner(
@@ -209,7 +226,7 @@ describe("set owners", async () => {
     );
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [setOwnersAndChangeThresholdUsingOwner], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress2);
-    let txResult2 = await dsl.executeTransaction(txAddress2, setOwnersAndChangeThresholdUsingOwner, multisig.signer, multisig.address, ownerC, ownerA.publicKey);
+    let txResult2 = await dsl.executeTransaction(txAddress2, setOwnersAndChangeThresholdUsingOwner, multisig.signer, multisig.address, ownerC, context.payer.publicKey);
     assert.strictEqual(txResult2.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
   });
 });
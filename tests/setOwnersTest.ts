@@ -19,12 +19,12 @@ describe("set owners", async () => {
     const setOwners = dsl.createSetOwnersInstruction(multisig, newOwners);
     const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
-    await dsl.executeTransaction(transactionAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(transactionAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(actualMultisig["nonce"], multisig.nonce);
     assert.strictEqual(actualMultisig["threshold"], multisig.threshold);
-    assert.deepEqual(actualMultisig["owners"], newOwners.map(owner => Array.from(owner.toBytes())));
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), newOwners.map(owner => Array.from(owner.toBytes())));
     assert.strictEqual(actualMultisig["owner_set_seqno"], 1);
   });
 
@@ -36,16 +36,16 @@ describe("set owners", async () => {
     const shrinkOwnersInstruction = dsl.createSetOwnersInstruction(multisig, [ownerA.publicKey, ownerB.publicKey]);
     const [shrinkOwnersAddress, _txMeta1] = await dsl.proposeTransaction(ownerA, [shrinkOwnersInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, shrinkOwnersAddress);
-    await dsl.executeTransaction(shrinkOwnersAddress, shrinkOwnersInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(shrinkOwnersAddress, shrinkOwnersInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     // Create and execute instruction to re-expand multisig owners
     const expandOwnersInstruction = dsl.createSetOwnersInstruction(multisig, [ownerA.publicKey, ownerB.publicKey, ownerC.publicKey]);
     const [expandOwnersAddress, _txMeta2]= await dsl.proposeTransaction(ownerA, [expandOwnersInstruction], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, expandOwnersAddress);
-    await dsl.executeTransaction(expandOwnersAddress, expandOwnersInstruction, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(expandOwnersAddress, expandOwnersInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const actualMultisig = await dsl.getMultisig(multisig.address);
-    assert.deepStrictEqual(actualMultisig["owners"], [ownerA, ownerB, ownerC].map(o => Array.from(o.publicKey.toBytes())));
+    assert.deepStrictEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), [ownerA, ownerB, ownerC].map(o => Array.from(o.publicKey.toBytes())));
   });
 
   await test("can propose, sign and execute changing owners of 4/9 multisig within one transaction", async () => {
@@ -55,7 +55,7 @@ describe("set owners", async () => {
 
     const setOwners = dsl.createSetOwnersInstruction(multisig, [newOwner.publicKey, ...multisig.owners.slice(1).map(owner => owner.publicKey)]);
 
-    await dsl.proposeSignAndExecuteTransaction(multisig.owners[1], multisig.owners.slice(2, threshold + 1), [setOwners], multisig.signer, multisig.address, multisig.owners[1], multisig.owners[1].publicKey);
+    await dsl.proposeSignAndExecuteTransaction(multisig.owners[1], multisig.owners.slice(2, threshold + 1), [setOwners], multisig.signer, multisig.address, multisig.owners[1], context.payer.publicKey);
   })
 
   await test("should not allow old owners to propose new transaction after ownership change", async () => {
@@ -66,7 +66,7 @@ describe("set owners", async () => {
     const setOwners = dsl.createSetOwnersInstruction(multisig, [newOwnerA.publicKey, newOwnerB.publicKey, newOwnerC.publicKey]);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const setOwners2 = dsl.createSetOwnersInstruction(multisig, multisig.owners.map(owner => owner.publicKey));
 
@@ -86,7 +86,7 @@ describe("set owners", async () => {
     const setOwners = dsl.createSetOwnersInstruction(multisig, [newOwnerA.publicKey, newOwnerB.publicKey, newOwnerC.publicKey]);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const setOwners2 = dsl.createSetOwnersInstruction(multisig, multisig.owners.map(owner => owner.publicKey));
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(newOwnerA, [setOwners2], multisig.address);
@@ -113,7 +113,7 @@ describe("set owners", async () => {
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [transfer], multisig.address);
 
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const transactionAccount = await dsl.getTransactionAccount(txAddress2);
     const actualMultisig = await dsl.getMultisig(multisig.address);
@@ -145,14 +145,14 @@ describe("set owners", async () => {
 
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress2);
-    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     let txAccount = await dsl.getTransactionAccount(txAddress2);
     let actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(txAccount["owner_set_seqno"], 0, "Owner set sequence number should not have updated");
     assert.strictEqual(actualMultisig["owner_set_seqno"], 1, "Should have incremented owner set seq number");
 
-    const txMeta = await dsl.executeTransaction(txAddress2, transfer, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txMeta = await dsl.executeTransaction(txAddress2, transfer, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x8");
     assert(txMeta.meta.logMessages.some(log => log.endsWith(
         " assertion failed - program error: InvalidExecutor (The executor must be a signer and an owner of this multisig.)"
@@ -184,7 +184,7 @@ describe("set owners", async () => {
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
 
-    const txMeta = await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txMeta = await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x6");
     assert(txMeta.meta.logMessages.some(log => log.endsWith(
@@ -202,16 +202,16 @@ describe("set owners", async () => {
     const setOwners = dsl.createSetOwnersInstruction(multisig, newOwners);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
 
     const actualMultisig = await dsl.getMultisig(multisig.address);
     assert.strictEqual(actualMultisig["nonce"], multisig.nonce);
     assert.strictEqual(actualMultisig["threshold"], 1, "Should have updated threshold to owners length");
-    assert.deepEqual(actualMultisig["owners"], newOwners.map(owner => Array.from(owner.toBytes())), "Should have updated to new owners");
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), newOwners.map(owner => Array.from(owner.toBytes())), "Should have updated to new owners");
     assert.strictEqual(actualMultisig["owner_set_seqno"], 1, "Should have incremented owner set seq number");
   });
 
-  await test("should not allow increasing number of owners of multisig above its original value", async () => {
+  await test("should not allow increasing number of owners of multisig above its original value without a payer", async () => {
     const multisig = await dsl.createMultisig(2, 3);
     const [ownerA, ownerB, _ownerC] = multisig.owners;
     const [newOwnerA, newOwnerB, newOwnerC, newOwnerD] =
@@ -223,12 +223,33 @@ describe("set owners", async () => {
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
 
-    const txMeta = await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    const txMeta = await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x7");
-    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: TooManyOwners (The number of owners must not be increased above its original value.)")));
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: TooManyOwners (The number of owners can only be increased above its original value if a payer for the additional space is provided.)")));
     assert(txMeta.meta.logMessages.some(log => log.endsWith(" failed: custom program error: 0x7")));
   });
 
+  await test("should grow the multisig account when increasing the number of owners with the multisig signer as payer", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 10_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const newOwners = Array.from({length: 5}, () => Keypair.generate().publicKey);
+    const accountBefore = await context.banksClient.getAccount(multisig.address);
+
+    const setOwners = dsl.createSetOwnersInstruction(multisig, newOwners, [], [], multisig.signer);
+    const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwners], multisig.address);
+    await dsl.approveTransaction(ownerB, multisig.address, txAddress);
+    const txMeta = await dsl.executeTransaction(txAddress, setOwners, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), newOwners.map(owner => Array.from(owner.toBytes())));
+    const accountAfter = await context.banksClient.getAccount(multisig.address);
+    const rent = await context.banksClient.getRent();
+    assert.strictEqual(accountAfter.data.length, accountBefore.data.length + 2 * 35);
+    assert.strictEqual(BigInt(accountAfter.lamports), rent.minimumBalance(BigInt(accountAfter.data.length)));
+    await dsl.assertBalance(multisig.signer, 10_000_000 - (accountAfter.lamports - accountBefore.lamports));
+  });
+
   await test("should not allow to set owners without proposing a transaction", async () => {
     const multisig = await dsl.createMultisig(2, 3);
     const newOwners = [Keypair.generate().publicKey, Keypair.generate().publicKey, Keypair.generate().publicKey];
@@ -250,13 +271,13 @@ describe("set owners", async () => {
     const setOwnersUsingPayer = dsl.createSetOwnersInstructionManualSigner(dsl.programTestContext.payer.publicKey, multisig.address, newOwners);
     const [txAddress, _txMeta] = await dsl.proposeTransaction(ownerA, [setOwnersUsingPayer], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress);
-    let txResult = await dsl.executeTransaction(txAddress, setOwnersUsingPayer, multisig.signer, multisig.address, ownerB, ownerA.publicKey);
+    let txResult = await dsl.executeTransaction(txAddress, setOwnersUsingPayer, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
     assert.strictEqual(txResult.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
 
     const setOwnersUsingOwner = dsl.createSetOwnersInstructionManualSigner(ownerC.publicKey, multisig.address, newOwners);
     const [txAddress2, _txMeta2] = await dsl.proposeTransaction(ownerA, [setOwnersUsingOwner], multisig.address);
     await dsl.approveTransaction(ownerB, multisig.address, txAddress2);
-    let txResult2 = await dsl.executeTransaction(txAddress2, setOwnersUsingOwner, multisig.signer, multisig.address, ownerC, ownerA.publicKey);
+    let txResult2 = await dsl.executeTransaction(txAddress2, setOwnersUsingOwner, multisig.signer, multisig.address, ownerC, context.payer.publicKey);
     assert.strictEqual(txResult2.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
   });
 });
@@ -0,0 +1,115 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {findSpendingLimitAddress, MultisigAccount, MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("spending limits", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  async function addSpendingLimit(multisig: MultisigAccount, mint: PublicKey, amount: number, periodSeconds: number, members: PublicKey[]) {
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const addSpendingLimit = dsl.createAddSpendingLimitInstruction(multisig, 0, mint, amount, periodSeconds, members);
+    await dsl.proposeSignAndExecuteTransaction(ownerA, [], [addSpendingLimit], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    return findSpendingLimitAddress(multisig.address, 0, mint, programId);
+  }
+
+  await test("should transfer lamports within the spending limit without a proposal", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [_ownerA, ownerB, _ownerC] = multisig.owners;
+    const spendingLimitAddress = await addSpendingLimit(multisig, PublicKey.default, 1_000_000, 86_400, [ownerB.publicKey]);
+    const recipient = Keypair.generate().publicKey;
+
+    const txMeta = await dsl.useSpendingLimit(ownerB, multisig, 0, recipient, 600_000);
+
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(recipient, 600_000);
+    const spendingLimit = await dsl.getSpendingLimit(spendingLimitAddress);
+    assert.strictEqual(spendingLimit["amount"], 1_000_000n);
+    assert.strictEqual(spendingLimit["remaining_amount"], 400_000n);
+  });
+
+  await test("should not transfer more than the remaining amount of the current period", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [_ownerA, ownerB, _ownerC] = multisig.owners;
+    await addSpendingLimit(multisig, PublicKey.default, 1_000_000, 86_400, [ownerB.publicKey]);
+    const recipient = Keypair.generate().publicKey;
+    await dsl.useSpendingLimit(ownerB, multisig, 0, recipient, 600_000);
+
+    const txMeta = await dsl.useSpendingLimit(ownerB, multisig, 0, recipient, 600_000);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x22");
+    assert(txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: SpendingLimitExceeded (The amount exceeds the remaining spending limit of the current period.)")));
+    await dsl.assertBalance(recipient, 600_000);
+  });
+
+  await test("should restore the full amount once the period has elapsed", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [_ownerA, ownerB, _ownerC] = multisig.owners;
+    await addSpendingLimit(multisig, PublicKey.default, 1_000_000, 3_600, [ownerB.publicKey]);
+    const recipient = Keypair.generate().publicKey;
+    await dsl.useSpendingLimit(ownerB, multisig, 0, recipient, 1_000_000);
+
+    await dsl.advanceClock(3_600);
+    const txMeta = await dsl.useSpendingLimit(ownerB, multisig, 0, recipient, 1_000_000);
+
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(recipient, 2_000_000);
+  });
+
+  await test("should not let an owner use a spending limit they are not a member of", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [_ownerA, ownerB, ownerC] = multisig.owners;
+    await addSpendingLimit(multisig, PublicKey.default, 1_000_000, 86_400, [ownerB.publicKey]);
+    const recipient = Keypair.generate().publicKey;
+
+    const txMeta = await dsl.useSpendingLimit(ownerC, multisig, 0, recipient, 1_000);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x20");
+    assert.strictEqual(await context.banksClient.getAccount(recipient), null);
+  });
+
+  await test("should not add a spending limit for a member that is not an owner", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const addSpendingLimit = dsl.createAddSpendingLimitInstruction(multisig, 0, PublicKey.default, 1_000_000, 86_400, [Keypair.generate().publicKey]);
+    const [transactionAddress, _proposeMeta] = await dsl.proposeTransaction(ownerA, [addSpendingLimit], multisig.address);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, addSpendingLimit, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x20");
+    assert.strictEqual(await context.banksClient.getAccount(findSpendingLimitAddress(multisig.address, 0, PublicKey.default, programId)), null);
+  });
+
+  await test("should transfer tokens within the spending limit of their mint", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [_ownerA, ownerB, _ownerC] = multisig.owners;
+    const mint = await dsl.createTokenMint(3);
+    const vaultAta = await dsl.createAta(mint, multisig.signer, 20);
+    const destinationAta = await dsl.createAta(mint, Keypair.generate().publicKey);
+    await addSpendingLimit(multisig, mint.account, 15, 86_400, [ownerB.publicKey]);
+
+    const txMeta = await dsl.useTokenSpendingLimit(ownerB, multisig, 0, mint, vaultAta, destinationAta, 10);
+    const exceededTxMeta = await dsl.useTokenSpendingLimit(ownerB, multisig, 0, mint, vaultAta, destinationAta, 6);
+
+    assert.isNull(txMeta.result);
+    assert.strictEqual(exceededTxMeta.result, "Error processing Instruction 0: custom program error: 0x22");
+    await dsl.assertTokenAccountBalance(vaultAta, 10);
+    await dsl.assertTokenAccountBalance(destinationAta, 10);
+  });
+
+  await test("should close the spending limit account when it is removed", async () => {
+    const multisig = await dsl.createMultisig(1, 3, 10_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const spendingLimitAddress = await addSpendingLimit(multisig, PublicKey.default, 1_000_000, 86_400, [ownerB.publicKey]);
+    const removeSpendingLimit = dsl.createRemoveSpendingLimitInstruction(multisig, spendingLimitAddress, ownerA.publicKey);
+
+    await dsl.proposeSignAndExecuteTransaction(ownerA, [], [removeSpendingLimit], multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+    const txMeta = await dsl.useSpendingLimit(ownerB, multisig, 0, Keypair.generate().publicKey, 1_000);
+
+    assert.strictEqual(await context.banksClient.getAccount(spendingLimitAddress), null);
+    assert.isNotNull(txMeta.result);
+  });
+});
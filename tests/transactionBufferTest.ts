@@ -0,0 +1,140 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {createCloseTransactionBufferInstruction, createExtendTransactionBufferInstruction, MultisigDsl, parseMultisigEvents} from "../ts";
+import {assert} from "chai";
+import {createHash} from "node:crypto";
+
+describe("transaction buffers", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  // a dozen of these transfers is too large to be proposed within a single packet
+  function payouts(multisigSigner: PublicKey, recipients: PublicKey[]) {
+    return recipients.map(recipient => SystemProgram.transfer({
+      fromPubkey: multisigSigner,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    }));
+  }
+
+  await test("should propose and execute a transaction uploaded in chunks", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 20_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const recipients = Array.from({length: 12}, () => Keypair.generate().publicKey);
+    const instructions = payouts(multisig.signer, recipients);
+
+    const [transactionBuffer, uploadMeta] = await dsl.uploadTransactionBuffer(ownerA, instructions, multisig.address);
+    const [transactionAddress, finalizeMeta] = await dsl.finalizeTransactionBuffer(ownerA, multisig.address);
+
+    assert.isNull(uploadMeta.result);
+    assert.isNull(finalizeMeta.result);
+    assert.strictEqual(await context.banksClient.getAccount(transactionBuffer), null);
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.strictEqual(transaction["instructions"].length, 12);
+    assert.deepStrictEqual(transaction["signers"], [true, false, false]);
+    assert.deepStrictEqual(transaction["proposer"], Array.from(ownerA.publicKey.toBytes()));
+    const finalizeEvents = parseMultisigEvents(finalizeMeta.meta.logMessages);
+    assert.deepStrictEqual(finalizeEvents.map(event => Object.keys(event)[0]), ["TransactionProposed", "TransactionBufferClosed"]);
+    assert.deepStrictEqual(finalizeEvents[1], {TransactionBufferClosed: {
+      multisig: Array.from(multisig.address.toBytes()),
+      transaction_buffer: Array.from(transactionBuffer.toBytes()),
+      creator: Array.from(ownerA.publicKey.toBytes()),
+    }});
+
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+    const executeMeta = await dsl.executeTransactionWithMultipleInstructions(
+        transactionAddress, instructions, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(executeMeta.result);
+    for (const recipient of recipients) {
+      await dsl.assertBalance(recipient, 1_000_000);
+    }
+  });
+
+  await test("should carry the memo and description hash of the buffer over to the transaction", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const instructions = payouts(multisig.signer, Array.from({length: 12}, () => Keypair.generate().publicKey));
+    const descriptionHash = createHash("sha256").update("Monthly payroll").digest();
+
+    await dsl.uploadTransactionBuffer(ownerA, instructions, multisig.address, 800, 0, undefined, {memo: "payroll", descriptionHash});
+    const [transactionAddress, finalizeMeta] = await dsl.finalizeTransactionBuffer(ownerA, multisig.address);
+
+    assert.isNull(finalizeMeta.result);
+    const transaction = await dsl.getTransactionAccount(transactionAddress);
+    assert.strictEqual(transaction["memo"], "payroll");
+    assert.deepStrictEqual(transaction["description_hash"], Array.from(descriptionHash));
+  });
+
+  await test("should not finalize a transaction buffer that does not match its hash", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const instructions = payouts(multisig.signer, Array.from({length: 12}, () => Keypair.generate().publicKey));
+
+    const [transactionBuffer, uploadMeta] = await dsl.uploadTransactionBuffer(ownerA, instructions, multisig.address, 800, 0, new Uint8Array(32));
+    const [transactionAddress, finalizeMeta] = await dsl.finalizeTransactionBuffer(ownerA, multisig.address);
+
+    assert.isNull(uploadMeta.result);
+    assert.strictEqual(finalizeMeta.result, "Error processing Instruction 0: custom program error: 0x26");
+    assert(finalizeMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: TransactionBufferHashMismatch (The transaction buffer is incomplete or does not match its final hash.)")));
+    assert.isNotNull(await context.banksClient.getAccount(transactionBuffer));
+    assert.strictEqual(await context.banksClient.getAccount(transactionAddress), null);
+  });
+
+  await test("should let the creator close a transaction buffer that was never finalized, even once no longer an owner", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, ownerC] = multisig.owners;
+    const instructions = payouts(multisig.signer, Array.from({length: 12}, () => Keypair.generate().publicKey));
+    const [transactionBuffer, _uploadMeta] = await dsl.uploadTransactionBuffer(ownerA, instructions, multisig.address);
+    const bufferRent = (await context.banksClient.getAccount(transactionBuffer)).lamports;
+    await dsl.proposeSignAndExecuteTransaction(ownerB, [ownerC],
+        [dsl.createSetOwnersInstruction(multisig, [ownerB.publicKey, ownerC.publicKey])],
+        multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    const closeMeta = await dsl.closeTransactionBuffer(ownerA, multisig.address);
+
+    assert.isNull(closeMeta.result);
+    assert.strictEqual(await context.banksClient.getAccount(transactionBuffer), null);
+    await dsl.assertBalance(ownerA.publicKey, bufferRent);
+  });
+
+  await test("should only let the creator close a transaction buffer", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const instructions = payouts(multisig.signer, [Keypair.generate().publicKey]);
+    const [transactionBuffer, _uploadMeta] = await dsl.uploadTransactionBuffer(ownerA, instructions, multisig.address);
+
+    const close = createCloseTransactionBufferInstruction(transactionBuffer, ownerB.publicKey, programId);
+    const txMeta = await dsl.createAndProcessTx([close], context.payer, [ownerB]);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x24");
+    assert.isNotNull(await context.banksClient.getAccount(transactionBuffer));
+  });
+
+  await test("should only let the creator extend a transaction buffer", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const instructions = payouts(multisig.signer, [Keypair.generate().publicKey]);
+    const [transactionBuffer, _uploadMeta] = await dsl.uploadTransactionBuffer(ownerA, instructions, multisig.address);
+
+    const extend = createExtendTransactionBufferInstruction(transactionBuffer, ownerB.publicKey, context.payer.publicKey, new Uint8Array(10), programId);
+    const txMeta = await dsl.createAndProcessTx([extend], context.payer, [ownerB]);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x24");
+  });
+
+  await test("should not extend a transaction buffer beyond its final size", async () => {
+    const multisig = await dsl.createMultisig(2, 3);
+    const [ownerA, _ownerB, _ownerC] = multisig.owners;
+    const instructions = payouts(multisig.signer, [Keypair.generate().publicKey]);
+    const [transactionBuffer, _uploadMeta] = await dsl.uploadTransactionBuffer(ownerA, instructions, multisig.address);
+
+    const extend = createExtendTransactionBufferInstruction(transactionBuffer, ownerA.publicKey, context.payer.publicKey, new Uint8Array(10), programId);
+    const txMeta = await dsl.createAndProcessTx([extend], context.payer, [ownerA]);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0x25");
+    assert.strictEqual((await dsl.getTransactionBuffer(transactionBuffer))["buffer"].length, (await dsl.getTransactionBuffer(transactionBuffer))["final_size"]);
+  });
+});
@@ -0,0 +1,68 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {findVaultAddress, MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("vaults", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should execute transaction signed by the vault it was proposed for", async () => {
+    const multisig = await dsl.createMultisig(2, 3, 1_000_000);
+    const [ownerA, ownerB, _ownerC] = multisig.owners;
+    const treasury = findVaultAddress(multisig.address, 1, programId);
+    await dsl.fund(treasury, 1_000_000);
+    const recipient = Keypair.generate().publicKey;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: treasury,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransactionForVault(ownerA, [transactionInstruction], multisig.address, 1);
+    await dsl.approveTransaction(ownerB, multisig.address, transactionAddress);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, treasury, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+    await dsl.assertBalance(treasury, 0);
+    await dsl.assertBalance(multisig.signer, 1_000_000);
+  });
+
+  await test("should not execute transaction with the signer of a different vault", async () => {
+    const multisig = await dsl.createMultisig(1, 2, 1_000_000);
+    const [ownerA, _ownerB] = multisig.owners;
+    const operations = findVaultAddress(multisig.address, 2, programId);
+    await dsl.fund(operations, 1_000_000);
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransactionForVault(ownerA, [transactionInstruction], multisig.address, 2);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
+    await dsl.assertBalance(multisig.signer, 1_000_000);
+  });
+
+  await test("should not let a vault other than vault 0 change the multisig", async () => {
+    const multisig = await dsl.createMultisig(1, 2);
+    const [ownerA, ownerB] = multisig.owners;
+    const treasury = findVaultAddress(multisig.address, 1, programId);
+
+    const setOwners = dsl.createSetOwnersInstructionManualSigner(treasury, multisig.address, [ownerA.publicKey]);
+    const [transactionAddress, _txMeta] = await dsl.proposeTransactionForVault(ownerA, [setOwners], multisig.address, 1);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, setOwners, treasury, multisig.address, ownerA, context.payer.publicKey);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: Provided seeds do not result in a valid address");
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["pubkey"]), [ownerA, ownerB].map(owner => Array.from(owner.publicKey.toBytes())));
+  });
+});
@@ -0,0 +1,85 @@
+import {describe, test} from "node:test";
+import {Keypair, PublicKey, SystemProgram} from "@solana/web3.js";
+import {start} from "solana-bankrun";
+import {MultisigDsl} from "../ts";
+import {assert} from "chai";
+
+describe("weighted voting", async () => {
+  const programId = PublicKey.unique();
+  const context = await start([{ name: "multisig_native", programId: programId }], []);
+  const dsl = new MultisigDsl(programId, context);
+
+  await test("should create multisig with owner weights", async () => {
+    const multisig = await dsl.createMultisigWithWeights(4, [3, 1, 1]);
+
+    const actualMultisig = await dsl.getMultisig(multisig.address);
+    assert.deepEqual(actualMultisig["owners"].map(owner => owner["weight"]), [3, 1, 1]);
+    assert.strictEqual(actualMultisig["threshold"], 4);
+  });
+
+  await test("should execute transaction once the approval weight reaches the threshold", async () => {
+    const multisig = await dsl.createMultisigWithWeights(4, [3, 1, 1], 1_000_000);
+    const [cfo, _ownerB, ownerC] = multisig.owners;
+    const recipient = Keypair.generate().publicKey;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: recipient,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(cfo, [transactionInstruction], multisig.address);
+    await dsl.approveTransaction(ownerC, multisig.address, transactionAddress);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, cfo, context.payer.publicKey);
+
+    assert.isNull(txMeta.result);
+    await dsl.assertBalance(recipient, 1_000_000);
+  });
+
+  await test("should not execute transaction approved by a majority of owners below the threshold weight", async () => {
+    const multisig = await dsl.createMultisigWithWeights(3, [3, 1, 1], 1_000_000);
+    const [_cfo, ownerB, ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerB, [transactionInstruction], multisig.address);
+    await dsl.approveTransaction(ownerC, multisig.address, transactionAddress);
+
+    const txMeta = await dsl.executeTransaction(transactionAddress, transactionInstruction, multisig.signer, multisig.address, ownerB, context.payer.publicKey);
+
+    assert.strictEqual(txMeta.result, "Error processing Instruction 0: custom program error: 0xd");
+    await dsl.assertBalance(multisig.signer, 1_000_000);
+  });
+
+  await test("should reject transaction once the remaining weight cannot reach the threshold", async () => {
+    const multisig = await dsl.createMultisigWithWeights(3, [3, 1, 1]);
+    const [cfo, ownerB, _ownerC] = multisig.owners;
+
+    const transactionInstruction = SystemProgram.transfer({
+      fromPubkey: multisig.signer,
+      lamports: 1_000,
+      toPubkey: Keypair.generate().publicKey,
+    });
+    const [transactionAddress, _txMeta] = await dsl.proposeTransaction(ownerB, [transactionInstruction], multisig.address);
+
+    await dsl.rejectTransaction(cfo, multisig.address, transactionAddress);
+
+    assert.isTrue((await dsl.getTransactionAccount(transactionAddress))["rejected"]);
+  });
+
+  await test("should not create multisig with threshold above the total weight", async () => {
+    const multisig = await dsl.createMultisigWithWeights(6, [3, 1, 1]);
+
+    assert.strictEqual(multisig.txMeta.result, "Error processing Instruction 0: custom program error: 0x0");
+  });
+
+  await test("should not create multisig with a voting owner without weight", async () => {
+    const multisig = await dsl.createMultisigWithWeights(1, [1, 0]);
+
+    assert(multisig.txMeta.meta.logMessages.some(log => log.endsWith(" assertion failed - program error: InvalidWeight (Owners with vote permission must have a weight greater than zero.)")));
+    assert.strictEqual(multisig.txMeta.result, "Error processing Instruction 0: custom program error: 0x1b");
+  });
+});
@@ -0,0 +1,121 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {OwnerSchema} from "./state/multisig";
+
+const PublicKeySchema = {array: {type: "u8", len: 32}};
+
+// Mirrors `MultisigEvent` of the program, decoded into an object keyed by the name of the event,
+// e.g. `{TransactionApproved: {multisig, transaction, approver, approval_weight, threshold_reached_at}}`.
+export const MultisigEventSchema =
+  {
+    enum: [
+      {struct: {MultisigCreated: {struct: {
+        multisig: PublicKeySchema,
+        owners: {array: {type: OwnerSchema}},
+        threshold: "u16",
+        cancel_threshold: "u8",
+        time_lock_seconds: "u32",
+      }}}},
+      {struct: {OwnersChanged: {struct: {
+        multisig: PublicKeySchema,
+        owners: {array: {type: OwnerSchema}},
+        owner_set_seqno: "u32",
+        threshold: "u16",
+        cancel_threshold: "u8",
+      }}}},
+      {struct: {ThresholdChanged: {struct: {multisig: PublicKeySchema, threshold: "u16"}}}},
+      {struct: {CancelThresholdChanged: {struct: {multisig: PublicKeySchema, cancel_threshold: "u8"}}}},
+      {struct: {TimeLockChanged: {struct: {multisig: PublicKeySchema, time_lock_seconds: "u32"}}}},
+      {struct: {TransactionProposed: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        proposer: PublicKeySchema,
+        transaction_index: "u64",
+        vault_index: "u8",
+        approval_weight: "u32",
+        threshold_reached_at: "i64",
+      }}}},
+      {struct: {TransactionApproved: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        approver: PublicKeySchema,
+        approval_weight: "u32",
+        threshold_reached_at: "i64",
+      }}}},
+      {struct: {ApprovalRevoked: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        owner: PublicKeySchema,
+        approval_weight: "u32",
+        threshold_reached_at: "i64",
+      }}}},
+      {struct: {TransactionRejected: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        rejector: PublicKeySchema,
+        rejection_weight: "u32",
+        rejected: "bool",
+      }}}},
+      {struct: {TransactionExecuted: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        executor: PublicKeySchema,
+      }}}},
+      {struct: {CancelVoted: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        canceller: PublicKeySchema,
+        cancellation_count: "u8",
+      }}}},
+      {struct: {TransactionCancelled: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        canceller: PublicKeySchema,
+      }}}},
+      {struct: {StaleTransactionClosed: {struct: {multisig: PublicKeySchema, transaction: PublicKeySchema}}}},
+      {struct: {InstructionsExecuted: {struct: {
+        multisig: PublicKeySchema,
+        transaction: PublicKeySchema,
+        executor: PublicKeySchema,
+        executed_up_to: "u16",
+      }}}},
+      {struct: {MultisigClosed: {struct: {multisig: PublicKeySchema, recipient: PublicKeySchema}}}},
+      {struct: {SpendingLimitAdded: {struct: {
+        multisig: PublicKeySchema,
+        spending_limit: PublicKeySchema,
+        vault_index: "u8",
+        mint: PublicKeySchema,
+        amount: "u64",
+        period_seconds: "i64",
+        members: {array: {type: PublicKeySchema}},
+      }}}},
+      {struct: {SpendingLimitRemoved: {struct: {multisig: PublicKeySchema, spending_limit: PublicKeySchema}}}},
+      {struct: {SpendingLimitUsed: {struct: {
+        multisig: PublicKeySchema,
+        spending_limit: PublicKeySchema,
+        member: PublicKeySchema,
+        destination: PublicKeySchema,
+        amount: "u64",
+        remaining_amount: "u64",
+      }}}},
+      {struct: {TransactionBufferCreated: {struct: {
+        multisig: PublicKeySchema,
+        transaction_buffer: PublicKeySchema,
+        creator: PublicKeySchema,
+        buffer_index: "u8",
+        final_size: "u32",
+      }}}},
+      {struct: {TransactionBufferClosed: {struct: {
+        multisig: PublicKeySchema,
+        transaction_buffer: PublicKeySchema,
+        creator: PublicKeySchema,
+      }}}},
+    ]
+  };
+
+// Decodes the events logged by the program ("Program data: <base64>" entries) from the log messages of a transaction.
+export function parseMultisigEvents(logMessages: string[]): any[] {
+  return logMessages
+      .filter(log => log.startsWith("Program data: "))
+      .map(log => borsh.deserialize(MultisigEventSchema, Buffer.from(log.substring("Program data: ".length), "base64")));
+}
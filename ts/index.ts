@@ -1,3 +1,4 @@
 export * from "./instructions";
 export * from "./multisigDsl";
 export * from './state';
+export * from "./events";
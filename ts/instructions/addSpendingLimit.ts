@@ -0,0 +1,55 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+import {findSpendingLimitAddress} from "../state/spendingLimit";
+
+export function createAddSpendingLimitInstruction(
+    multisigSigner: PublicKey,
+    multisigAccount: PublicKey,
+    payer: PublicKey,
+    vaultIndex: number,
+    mint: PublicKey,
+    amount: bigint | number,
+    periodSeconds: bigint | number,
+    members: PublicKey[],
+    programId: PublicKey): TransactionInstruction {
+  const addSpendingLimit = new AddSpendingLimit({
+    instructionDiscriminator: MultisigInstruction.AddSpendingLimit,
+    vault_index: vaultIndex,
+    mint: mint.toBuffer(),
+    amount: amount,
+    period_seconds: periodSeconds,
+    members: members.map(member => member.toBuffer())
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: multisigAccount, isSigner: false, isWritable: true },
+      { pubkey: multisigSigner, isSigner: true, isWritable: false },
+      { pubkey: findSpendingLimitAddress(multisigAccount, vaultIndex, mint, programId), isSigner: false, isWritable: true },
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId: programId,
+    data: addSpendingLimit.toBuffer(),
+  });
+}
+
+export class AddSpendingLimit extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(AddSpendingLimitSchema, this));
+  }
+}
+
+const AddSpendingLimitSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8",
+      vault_index: "u8",
+      mint: {array: {type: "u8", len: 32}},
+      amount: "u64",
+      period_seconds: "i64",
+      members: {array: {type: {array: {type: "u8", len: 32}}}}
+    }
+  };
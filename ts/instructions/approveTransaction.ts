@@ -10,9 +10,11 @@ export function createApproveTransactionInstruction(multisigAccount: PublicKey,
                                                     programId: PublicKey,
                                                     approverIsSigner: boolean = true,
                                                     transactionIsWritable: boolean = true,
+                                                    expectedHash: Uint8Array | null = null,
                                                     ): TransactionInstruction {
   const approveTransactionInstruction = new ApproveTransactionInstruction({
     instructionDiscriminator: MultisigInstruction.ApproveTransaction,
+    expected_hash: expectedHash,
   });
   return new TransactionInstruction({
     keys: [
@@ -33,4 +35,5 @@ class ApproveTransactionInstruction extends Assignable {
 
 const ApproveTransactionSchema = { struct: {
   instructionDiscriminator: "u8",
+  expected_hash: { option: { array: { type: "u8", len: 32 }}},
 }};
@@ -0,0 +1,34 @@
+import {Buffer} from "node:buffer";
+import {PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction} from "@solana/web3.js";
+import * as borsh from "borsh";
+import {MultisigInstruction} from ".";
+import {Assignable} from "../assignable";
+
+// Approves the transaction for every owner whose signature over its content hash (see `transactionContentHash`) is
+// verified by an ed25519 program instruction in the same transaction.
+export function createApproveWithSignaturesInstruction(multisigAccount: PublicKey,
+                                                       transactionAccount: PublicKey,
+                                                       programId: PublicKey): TransactionInstruction {
+  const approveWithSignaturesInstruction = new ApproveWithSignaturesInstruction({
+    instructionDiscriminator: MultisigInstruction.ApproveWithSignatures,
+  });
+  return new TransactionInstruction({
+    keys: [
+      {pubkey: multisigAccount, isSigner: false, isWritable: false},
+      {pubkey: transactionAccount, isSigner: false, isWritable: true},
+      {pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false},
+    ],
+    programId: programId,
+    data: approveWithSignaturesInstruction.toBuffer(),
+  });
+}
+
+class ApproveWithSignaturesInstruction extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(ApproveWithSignaturesSchema, this));
+  }
+}
+
+const ApproveWithSignaturesSchema = { struct: {
+  instructionDiscriminator: "u8",
+}};
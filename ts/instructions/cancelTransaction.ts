@@ -1,5 +1,5 @@
 import {Buffer} from "node:buffer";
-import {type PublicKey, TransactionInstruction} from "@solana/web3.js";
+import {AccountMeta, PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
 import * as borsh from "borsh";
 import {MultisigInstruction} from ".";
 import {Assignable} from "../assignable";
@@ -8,16 +8,24 @@ export function createCancelTransactionInstruction(multisigAccount: PublicKey,
                                                    transactionAccount: PublicKey,
                                                    refundee: PublicKey,
                                                    executor: PublicKey,
-                                                   programId: PublicKey): TransactionInstruction {
+                                                   programId: PublicKey,
+                                                   payer: PublicKey | null = null): TransactionInstruction {
   const cancelTransactionInstruction = new CancelTransactionInstruction({
     instructionDiscriminator: MultisigInstruction.CancelTransaction,
   });
-  const accounts = [
-    {pubkey: multisigAccount, isSigner: false, isWritable: false},
+  const accounts: AccountMeta[] = [
+    {pubkey: multisigAccount, isSigner: false, isWritable: true},
     {pubkey: transactionAccount, isSigner: false, isWritable: true},
     {pubkey: refundee, isSigner: false, isWritable: true},
     {pubkey: executor, isSigner: true, isWritable: false},
   ];
+  // a payer is only required to record the cancel votes of a larger owner set than the one of the proposal
+  if (payer) {
+    accounts.push(
+      {pubkey: payer, isSigner: true, isWritable: true},
+      {pubkey: SystemProgram.programId, isSigner: false, isWritable: false},
+    );
+  }
   return new TransactionInstruction({
     keys: accounts,
     programId: programId,
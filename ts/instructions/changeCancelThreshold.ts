@@ -0,0 +1,38 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+export function createChangeCancelThresholdInstruction(
+    multisigSigner: PublicKey,
+    multisigAccount: PublicKey,
+    cancelThreshold: number,
+    programId: PublicKey): TransactionInstruction {
+  const changeCancelThreshold = new ChangeCancelThreshold({
+    instructionDiscriminator: MultisigInstruction.ChangeCancelThreshold,
+    cancel_threshold: cancelThreshold
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: multisigAccount, isSigner: false, isWritable: true },
+      { pubkey: multisigSigner, isSigner: true, isWritable: false },
+    ],
+    programId: programId,
+    data: changeCancelThreshold.toBuffer(),
+  });
+}
+
+export class ChangeCancelThreshold extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(ChangeCancelThresholdSchema, this));
+  }
+}
+
+const ChangeCancelThresholdSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8",
+      cancel_threshold: "u8"
+    }
+  };
@@ -33,6 +33,6 @@ const ChangeThresholdSchema =
   {
     struct: {
       instructionDiscriminator: "u8",
-      threshold: "u8"
+      threshold: "u16"
     }
   };
@@ -0,0 +1,38 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+export function createChangeTimeLockInstruction(
+    multisigSigner: PublicKey,
+    multisigAccount: PublicKey,
+    timeLockSeconds: number,
+    programId: PublicKey): TransactionInstruction {
+  const changeTimeLock = new ChangeTimeLock({
+    instructionDiscriminator: MultisigInstruction.ChangeTimeLock,
+    time_lock_seconds: timeLockSeconds
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: multisigAccount, isSigner: false, isWritable: true },
+      { pubkey: multisigSigner, isSigner: true, isWritable: false },
+    ],
+    programId: programId,
+    data: changeTimeLock.toBuffer(),
+  });
+}
+
+export class ChangeTimeLock extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(ChangeTimeLockSchema, this));
+  }
+}
+
+const ChangeTimeLockSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8",
+      time_lock_seconds: "u32"
+    }
+  };
@@ -0,0 +1,38 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+export function createCloseMultisigInstruction(
+    multisigSigner: PublicKey,
+    multisigAccount: PublicKey,
+    recipient: PublicKey,
+    programId: PublicKey): TransactionInstruction {
+  const closeMultisig = new CloseMultisig({
+    instructionDiscriminator: MultisigInstruction.CloseMultisig,
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: multisigAccount, isSigner: false, isWritable: true },
+      { pubkey: multisigSigner, isSigner: true, isWritable: true },
+      { pubkey: recipient, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId: programId,
+    data: closeMultisig.toBuffer(),
+  });
+}
+
+export class CloseMultisig extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(CloseMultisigSchema, this));
+  }
+}
+
+const CloseMultisigSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8"
+    }
+  };
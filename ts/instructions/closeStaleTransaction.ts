@@ -0,0 +1,34 @@
+import {Buffer} from "node:buffer";
+import {type PublicKey, TransactionInstruction} from "@solana/web3.js";
+import * as borsh from "borsh";
+import {MultisigInstruction} from ".";
+import {Assignable} from "../assignable";
+
+export function createCloseStaleTransactionInstruction(multisigAccount: PublicKey,
+                                                       transactionAccount: PublicKey,
+                                                       rentPayer: PublicKey,
+                                                       programId: PublicKey): TransactionInstruction {
+  const closeStaleTransactionInstruction = new CloseStaleTransactionInstruction({
+    instructionDiscriminator: MultisigInstruction.CloseStaleTransaction,
+  });
+  const accounts = [
+    {pubkey: multisigAccount, isSigner: false, isWritable: true},
+    {pubkey: transactionAccount, isSigner: false, isWritable: true},
+    {pubkey: rentPayer, isSigner: false, isWritable: true},
+  ];
+  return new TransactionInstruction({
+    keys: accounts,
+    programId: programId,
+    data: closeStaleTransactionInstruction.toBuffer(),
+  });
+}
+
+class CloseStaleTransactionInstruction extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(CloseStaleTransactionSchema, this));
+  }
+}
+
+const CloseStaleTransactionSchema = { struct: {
+  instructionDiscriminator: "u8",
+}};
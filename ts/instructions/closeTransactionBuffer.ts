@@ -0,0 +1,36 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+// The creator receives the rent of the closed buffer.
+export function createCloseTransactionBufferInstruction(
+    transactionBuffer: PublicKey,
+    creator: PublicKey,
+    programId: PublicKey): TransactionInstruction {
+  const closeTransactionBuffer = new CloseTransactionBuffer({
+    instructionDiscriminator: MultisigInstruction.CloseTransactionBuffer,
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: transactionBuffer, isSigner: false, isWritable: true },
+      { pubkey: creator, isSigner: true, isWritable: true },
+    ],
+    programId: programId,
+    data: closeTransactionBuffer.toBuffer(),
+  });
+}
+
+export class CloseTransactionBuffer extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(CloseTransactionBufferSchema, this));
+  }
+}
+
+const CloseTransactionBufferSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8"
+    }
+  };
@@ -3,6 +3,19 @@ import * as borsh from "borsh";
 import {Assignable} from "../assignable";
 import {Keypair, PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
 import {MultisigInstruction} from "./index";
+import {OwnerSchema, Permission, toOwners} from "../state/multisig";
+
+/**
+ * The optional parameters of a new multisig (each owner gets all permissions and a weight of 1 by default, and a single
+ * owner can cancel a transaction unless a cancel threshold is given).
+ */
+export interface CreateMultisigOptions {
+  systemProgramId?: PublicKey;
+  timeLockSeconds?: number;
+  cancelThreshold?: number | null;
+  permissions?: Permission[];
+  weights?: number[];
+}
 
 export function createCreateMultisigInstruction(programId: PublicKey,
                                                 threshold: number,
@@ -11,12 +24,20 @@ export function createCreateMultisigInstruction(programId: PublicKey,
                                                 multisigAccount: PublicKey,
                                                 multisigSigner: PublicKey,
                                                 payer: PublicKey,
-                                                systemProgramId: PublicKey = SystemProgram.programId): TransactionInstruction {
+                                                {
+                                                  systemProgramId = SystemProgram.programId,
+                                                  timeLockSeconds = 0,
+                                                  cancelThreshold = null,
+                                                  permissions = [],
+                                                  weights = []
+                                                }: CreateMultisigOptions = {}): TransactionInstruction {
   const createMultisig = new CreateMultisig({
     instructionDiscriminator: MultisigInstruction.CreateMultisig,
-    owners: owners.map(owner => owner.publicKey.toBuffer()),
+    owners: toOwners(owners.map(owner => owner.publicKey), permissions, weights),
     threshold: threshold,
-    nonce: nonce
+    nonce: nonce,
+    time_lock_seconds: timeLockSeconds,
+    cancel_threshold: cancelThreshold
   });
   return new TransactionInstruction({
     keys: [
@@ -40,8 +61,10 @@ const CreateMultisigSchema =
   {
     struct: {
       instructionDiscriminator: "u8",
-      owners: {array: {type: {array: {type: "u8", len: 32}}}},
-      threshold: "u8",
-      nonce: "u8"
+      owners: {array: {type: OwnerSchema}},
+      threshold: "u16",
+      nonce: "u8",
+      time_lock_seconds: "u32",
+      cancel_threshold: {option: "u8"}
     }
   };
@@ -0,0 +1,72 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+import {TransactionExpiry, TransactionExpirySchema} from "./proposeTransaction";
+import {findTransactionBufferAddress} from "../state/transactionBuffer";
+
+// `finalHash` is the SHA-256 hash of the complete buffer, `buffer` its first chunk.
+export function createCreateTransactionBufferInstruction(
+    multisigAccount: PublicKey,
+    creator: PublicKey,
+    payer: PublicKey,
+    bufferIndex: number,
+    vaultIndex: number,
+    ephemeralSigners: number,
+    expiry: TransactionExpiry | null,
+    addressLookupTables: PublicKey[],
+    finalHash: Uint8Array,
+    finalSize: number,
+    buffer: Uint8Array,
+    programId: PublicKey,
+    memo: string | null = null,
+    descriptionHash: Uint8Array | null = null): TransactionInstruction {
+  const createTransactionBuffer = new CreateTransactionBuffer({
+    instructionDiscriminator: MultisigInstruction.CreateTransactionBuffer,
+    buffer_index: bufferIndex,
+    vault_index: vaultIndex,
+    ephemeral_signers: ephemeralSigners,
+    expiry: expiry,
+    address_lookup_tables: addressLookupTables.map(table => table.toBuffer()),
+    memo: memo,
+    description_hash: descriptionHash,
+    final_hash: finalHash,
+    final_size: finalSize,
+    buffer: buffer
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: multisigAccount, isSigner: false, isWritable: false },
+      { pubkey: findTransactionBufferAddress(multisigAccount, creator, bufferIndex, programId), isSigner: false, isWritable: true },
+      { pubkey: creator, isSigner: true, isWritable: false },
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId: programId,
+    data: createTransactionBuffer.toBuffer(),
+  });
+}
+
+export class CreateTransactionBuffer extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(CreateTransactionBufferSchema, this));
+  }
+}
+
+const CreateTransactionBufferSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8",
+      buffer_index: "u8",
+      vault_index: "u8",
+      ephemeral_signers: "u8",
+      expiry: {option: TransactionExpirySchema},
+      address_lookup_tables: {array: {type: {array: {type: "u8", len: 32}}}},
+      memo: {option: "string"},
+      description_hash: {option: {array: {type: "u8", len: 32}}},
+      final_hash: {array: {type: "u8", len: 32}},
+      final_size: "u32",
+      buffer: {array: {type: "u8"}}
+    }
+  };
@@ -11,12 +11,14 @@ export function createExecuteTransactionInstruction(multisigAccount: PublicKey,
                                                     executor: PublicKey,
                                                     remainingAccounts: AccountMeta[],
                                                     programId: PublicKey,
+                                                    maxInstructions: number | null = null,
                                                     ): TransactionInstruction {
   const executeTransactionInstruction = new ExecuteTransactionInstruction({
     instructionDiscriminator: MultisigInstruction.ExecuteTransaction,
+    max_instructions: maxInstructions,
   });
   const accounts = [
-    {pubkey: multisigAccount, isSigner: false, isWritable: false},
+    {pubkey: multisigAccount, isSigner: false, isWritable: true},
     {pubkey: multisigSigner, isSigner: false, isWritable: false},
     {pubkey: transactionAccount, isSigner: false, isWritable: true},
     {pubkey: refundee, isSigner: false, isWritable: true},
@@ -37,4 +39,5 @@ class ExecuteTransactionInstruction extends Assignable {
 
 const ExecuteTransactionSchema = { struct: {
   instructionDiscriminator: "u8",
+  max_instructions: { option: "u16" },
 }};
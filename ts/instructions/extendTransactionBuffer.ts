@@ -0,0 +1,41 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+export function createExtendTransactionBufferInstruction(
+    transactionBuffer: PublicKey,
+    creator: PublicKey,
+    payer: PublicKey,
+    buffer: Uint8Array,
+    programId: PublicKey): TransactionInstruction {
+  const extendTransactionBuffer = new ExtendTransactionBuffer({
+    instructionDiscriminator: MultisigInstruction.ExtendTransactionBuffer,
+    buffer: buffer
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: transactionBuffer, isSigner: false, isWritable: true },
+      { pubkey: creator, isSigner: true, isWritable: false },
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId: programId,
+    data: extendTransactionBuffer.toBuffer(),
+  });
+}
+
+export class ExtendTransactionBuffer extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(ExtendTransactionBufferSchema, this));
+  }
+}
+
+const ExtendTransactionBufferSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8",
+      buffer: {array: {type: "u8"}}
+    }
+  };
@@ -0,0 +1,46 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+// The creator becomes the proposer of the transaction and receives the rent of the closed buffer. The address lookup
+// tables of the buffer have to be passed along.
+export function createFinalizeTransactionBufferInstruction(
+    multisigAccount: PublicKey,
+    transactionAccount: PublicKey,
+    transactionBuffer: PublicKey,
+    creator: PublicKey,
+    payer: PublicKey,
+    programId: PublicKey,
+    addressLookupTables: PublicKey[] = []): TransactionInstruction {
+  const finalizeTransactionBuffer = new FinalizeTransactionBuffer({
+    instructionDiscriminator: MultisigInstruction.FinalizeTransactionBuffer,
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: multisigAccount, isSigner: false, isWritable: true },
+      { pubkey: transactionAccount, isSigner: false, isWritable: true },
+      { pubkey: transactionBuffer, isSigner: false, isWritable: true },
+      { pubkey: creator, isSigner: true, isWritable: true },
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...addressLookupTables.map(table => ({ pubkey: table, isSigner: false, isWritable: false })),
+    ],
+    programId: programId,
+    data: finalizeTransactionBuffer.toBuffer(),
+  });
+}
+
+export class FinalizeTransactionBuffer extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(FinalizeTransactionBufferSchema, this));
+  }
+}
+
+const FinalizeTransactionBufferSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8"
+    }
+  };
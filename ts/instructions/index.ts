@@ -6,6 +6,21 @@ export * from "./proposeTransaction";
 export * from "./approveTransaction";
 export * from "./executeTransaction";
 export * from "./cancelTransaction";
+export * from "./changeTimeLock";
+export * from "./closeStaleTransaction";
+export * from "./revokeApproval";
+export * from "./rejectTransaction";
+export * from "./changeCancelThreshold";
+export * from "./addSpendingLimit";
+export * from "./removeSpendingLimit";
+export * from "./useSpendingLimit";
+export * from "./createTransactionBuffer";
+export * from "./extendTransactionBuffer";
+export * from "./finalizeTransactionBuffer";
+export * from "./closeMultisig";
+export * from "./approveWithSignatures";
+export * from "./proposeConfigTransaction";
+export * from "./closeTransactionBuffer";
 
 export enum MultisigInstruction {
   CreateMultisig = 0,
@@ -16,4 +31,19 @@ export enum MultisigInstruction {
   ApproveTransaction = 5,
   ExecuteTransaction = 6,
   CancelTransaction = 7,
+  ChangeTimeLock = 8,
+  CloseStaleTransaction = 9,
+  RevokeApproval = 10,
+  RejectTransaction = 11,
+  ChangeCancelThreshold = 12,
+  AddSpendingLimit = 13,
+  RemoveSpendingLimit = 14,
+  UseSpendingLimit = 15,
+  CreateTransactionBuffer = 16,
+  ExtendTransactionBuffer = 17,
+  FinalizeTransactionBuffer = 18,
+  CloseMultisig = 19,
+  ApproveWithSignatures = 20,
+  ProposeConfigTransaction = 21,
+  CloseTransactionBuffer = 22,
 }
@@ -0,0 +1,66 @@
+import {Buffer} from "node:buffer";
+import {type PublicKey, TransactionInstruction} from "@solana/web3.js";
+import * as borsh from "borsh";
+import {MultisigInstruction} from ".";
+import {Assignable} from "../assignable";
+import {OwnerSchema} from "../state/multisig";
+import {TransactionExpiry, TransactionExpirySchema} from "./proposeTransaction";
+
+export function createProposeConfigTransactionInstruction(multisigAccount: PublicKey,
+                                                          transactionAccount: PublicKey,
+                                                          proposer: PublicKey,
+                                                          payer: PublicKey,
+                                                          programId: PublicKey,
+                                                          actions: ConfigAction[],
+                                                          systemProgramId: PublicKey,
+                                                          expiry: TransactionExpiry | null = null,
+                                                          memo: string | null = null,
+                                                          descriptionHash: Uint8Array | null = null,
+                                                          ): TransactionInstruction {
+  const proposeConfigTransactionInstruction = new ProposeConfigTransactionInstruction({
+    instructionDiscriminator: MultisigInstruction.ProposeConfigTransaction,
+    actions: actions,
+    expiry: expiry,
+    memo: memo,
+    description_hash: descriptionHash,
+  });
+  return new TransactionInstruction({
+    keys: [
+      {pubkey: multisigAccount, isSigner: false, isWritable: true},
+      {pubkey: transactionAccount, isSigner: false, isWritable: true},
+      {pubkey: proposer, isSigner: true, isWritable: false},
+      {pubkey: payer, isSigner: true, isWritable: true},
+      {pubkey: systemProgramId, isSigner: false, isWritable: false},
+    ],
+    programId: programId,
+    data: proposeConfigTransactionInstruction.toBuffer(),
+  });
+}
+
+/** A typed change to the multisig configuration, applied directly when the config transaction is executed. */
+export type ConfigAction =
+    { AddOwner: { pubkey: Uint8Array, permissions: number, weight: number } }
+    | { RemoveOwner: Uint8Array }
+    | { ChangeThreshold: number }
+    | { ChangeTimeLock: number };
+
+export const ConfigActionSchema = { enum: [
+  { struct: { AddOwner: OwnerSchema }},
+  { struct: { RemoveOwner: { array: { type: "u8", len: 32 }}}},
+  { struct: { ChangeThreshold: "u16" }},
+  { struct: { ChangeTimeLock: "u32" }},
+]};
+
+class ProposeConfigTransactionInstruction extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(ProposeConfigTransactionSchema, this));
+  }
+}
+
+const ProposeConfigTransactionSchema = { struct: {
+  instructionDiscriminator: "u8",
+  actions: { array: { type: ConfigActionSchema }},
+  expiry: { option: TransactionExpirySchema },
+  memo: { option: "string" },
+  description_hash: { option: { array: { type: "u8", len: 32 }}},
+}};
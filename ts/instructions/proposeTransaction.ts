@@ -1,9 +1,19 @@
 import {Buffer} from "node:buffer";
-import {type PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
+import {AddressLookupTableAccount, type PublicKey, TransactionInstruction} from "@solana/web3.js";
 import * as borsh from "borsh";
 import {MultisigInstruction} from ".";
 import {Assignable} from "../assignable";
 
+/** The optional parameters of a proposed transaction. */
+export interface ProposeTransactionOptions {
+  expiry?: TransactionExpiry | null;
+  vaultIndex?: number;
+  addressLookupTables?: AddressLookupTableAccount[];
+  ephemeralSigners?: number;
+  memo?: string | null;
+  descriptionHash?: Uint8Array | null;
+}
+
 export function createProposeTransactionInstruction(multisigAccount: PublicKey,
                                                     transactionAccount: PublicKey,
                                                     proposer: PublicKey,
@@ -12,45 +22,105 @@ export function createProposeTransactionInstruction(multisigAccount: PublicKey,
                                                     instructions: TransactionInstruction[],
                                                     proposerIsSigner,
                                                     systemProgramId,
-                                                    ): TransactionInstruction {
+                                                    {
+                                                      expiry = null,
+                                                      vaultIndex = 0,
+                                                      addressLookupTables = [],
+                                                      ephemeralSigners = 0,
+                                                      memo = null,
+                                                      descriptionHash = null
+                                                    }: ProposeTransactionOptions = {}): TransactionInstruction {
   const proposeTransactionInstruction = new ProposeTransactionInstruction({
     instructionDiscriminator: MultisigInstruction.ProposeTransaction,
-    instructions: instructions.map(ix => {
-      return {
-        program_id: ix.programId.toBuffer(),
-        accounts: ix.keys.map(key => { return { pubkey: key.pubkey.toBuffer(), is_signer: key.isSigner, is_writable: key.isWritable }}),
-        data: ix.data
-      };
-    })
+    instructions: instructions.map(ix => toTransactionInstructionData(ix, addressLookupTables)),
+    expiry: expiry,
+    vault_index: vaultIndex,
+    address_lookup_tables: addressLookupTables.map(table => table.key.toBuffer()),
+    ephemeral_signers: ephemeralSigners,
+    memo: memo,
+    description_hash: descriptionHash
   });
   return new TransactionInstruction({
     keys: [
-      {pubkey: multisigAccount, isSigner: false, isWritable: false},
-      {pubkey: transactionAccount, isSigner: true, isWritable: true},
+      {pubkey: multisigAccount, isSigner: false, isWritable: true},
+      {pubkey: transactionAccount, isSigner: false, isWritable: true},
       {pubkey: proposer, isSigner: proposerIsSigner, isWritable: false},
-      {pubkey: payer, isSigner: true, isWritable: false},
+      {pubkey: payer, isSigner: true, isWritable: true},
       {pubkey: systemProgramId, isSigner: false, isWritable: false},
+      // the lookup tables are read to check that the lookup accounts refer to existing addresses
+      ...addressLookupTables.map(table => ({pubkey: table.key, isSigner: false, isWritable: false})),
     ],
     programId: programId,
     data: proposeTransactionInstruction.toBuffer(),
   });
 }
 
+// Non-signer accounts found in one of the given address lookup tables are stored as lookup accounts (resolved on
+// execution), all others with their full pubkey.
+export function toTransactionInstructionData(ix: TransactionInstruction, addressLookupTables: AddressLookupTableAccount[] = []) {
+  const accounts = [];
+  const lookupAccounts = [];
+  ix.keys.forEach((key, position) => {
+    const tableIndex = key.isSigner ? -1 : addressLookupTables.findIndex(table =>
+        table.state.addresses.some(address => address.equals(key.pubkey)));
+    if (tableIndex >= 0) {
+      const addressIndex = addressLookupTables[tableIndex].state.addresses.findIndex(address => address.equals(key.pubkey));
+      lookupAccounts.push({ position: position, table_index: tableIndex, address_index: addressIndex, is_writable: key.isWritable });
+    } else {
+      accounts.push({ pubkey: key.pubkey.toBuffer(), is_signer: key.isSigner, is_writable: key.isWritable });
+    }
+  });
+  return {
+    program_id: ix.programId.toBuffer(),
+    accounts: accounts,
+    lookup_accounts: lookupAccounts,
+    data: ix.data
+  };
+}
+
+// The borsh encoding of the instructions as stored in a transaction (and uploaded to a transaction buffer).
+export function serializeTransactionInstructions(instructions: TransactionInstruction[], addressLookupTables: AddressLookupTableAccount[] = []): Buffer {
+  return Buffer.from(borsh.serialize(TransactionInstructionsSchema,
+      instructions.map(ix => toTransactionInstructionData(ix, addressLookupTables))));
+}
+
+/** Deadline after which the proposed transaction can no longer be approved or executed (inclusive). */
+export type TransactionExpiry = { Slot: bigint | number } | { UnixTimestamp: bigint | number };
+
+export const TransactionExpirySchema = { enum: [
+  { struct: { Slot: "u64" }},
+  { struct: { UnixTimestamp: "i64" }},
+]};
+
 class ProposeTransactionInstruction extends Assignable {
   toBuffer() {
     return Buffer.from(borsh.serialize(ProposeTransactionSchema, this));
   }
 }
 
+const TransactionInstructionsSchema = { array: { type: { struct: {
+  program_id: {array: {type: "u8", len: 32}},
+  accounts: { array: { type: { struct: {
+    pubkey: {array: {type: "u8", len: 32}},
+    is_signer: "bool",
+    is_writable: "bool"
+  }}}},
+  lookup_accounts: { array: { type: { struct: {
+    position: "u8",
+    table_index: "u8",
+    address_index: "u8",
+    is_writable: "bool"
+  }}}},
+  data: {array: {type: "u8"}}
+}}}};
+
 const ProposeTransactionSchema = { struct: {
   instructionDiscriminator: "u8",
-  instructions: { array: { type: { struct: {
-    program_id: {array: {type: "u8", len: 32}},
-    accounts: { array: { type: { struct: {
-      pubkey: {array: {type: "u8", len: 32}},
-      is_signer: "bool",
-      is_writable: "bool"
-    }}}},
-    data: {array: {type: "u8"}}
-  }}}},
+  instructions: TransactionInstructionsSchema,
+  expiry: { option: TransactionExpirySchema },
+  vault_index: "u8",
+  address_lookup_tables: { array: { type: { array: { type: "u8", len: 32 }}}},
+  ephemeral_signers: "u8",
+  memo: { option: "string" },
+  description_hash: { option: { array: { type: "u8", len: 32 }}},
 }};
@@ -0,0 +1,33 @@
+import {Buffer} from "node:buffer";
+import {type PublicKey, TransactionInstruction} from "@solana/web3.js";
+import * as borsh from "borsh";
+import {MultisigInstruction} from ".";
+import {Assignable} from "../assignable";
+
+export function createRejectTransactionInstruction(multisigAccount: PublicKey,
+                                                   transactionAccount: PublicKey,
+                                                   rejector: PublicKey,
+                                                   programId: PublicKey): TransactionInstruction {
+  const rejectTransactionInstruction = new RejectTransactionInstruction({
+    instructionDiscriminator: MultisigInstruction.RejectTransaction,
+  });
+  return new TransactionInstruction({
+    keys: [
+      {pubkey: multisigAccount, isSigner: false, isWritable: false},
+      {pubkey: transactionAccount, isSigner: false, isWritable: true},
+      {pubkey: rejector, isSigner: true, isWritable: false},
+    ],
+    programId: programId,
+    data: rejectTransactionInstruction.toBuffer(),
+  });
+}
+
+class RejectTransactionInstruction extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(RejectTransactionSchema, this));
+  }
+}
+
+const RejectTransactionSchema = { struct: {
+  instructionDiscriminator: "u8",
+}};
@@ -0,0 +1,39 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+export function createRemoveSpendingLimitInstruction(
+    multisigSigner: PublicKey,
+    multisigAccount: PublicKey,
+    spendingLimit: PublicKey,
+    refundee: PublicKey,
+    programId: PublicKey): TransactionInstruction {
+  const removeSpendingLimit = new RemoveSpendingLimit({
+    instructionDiscriminator: MultisigInstruction.RemoveSpendingLimit,
+  });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: multisigAccount, isSigner: false, isWritable: true },
+      { pubkey: multisigSigner, isSigner: true, isWritable: false },
+      { pubkey: spendingLimit, isSigner: false, isWritable: true },
+      { pubkey: refundee, isSigner: false, isWritable: true },
+    ],
+    programId: programId,
+    data: removeSpendingLimit.toBuffer(),
+  });
+}
+
+export class RemoveSpendingLimit extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(RemoveSpendingLimitSchema, this));
+  }
+}
+
+const RemoveSpendingLimitSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8"
+    }
+  };
@@ -0,0 +1,33 @@
+import {Buffer} from "node:buffer";
+import {type PublicKey, TransactionInstruction} from "@solana/web3.js";
+import * as borsh from "borsh";
+import {MultisigInstruction} from ".";
+import {Assignable} from "../assignable";
+
+export function createRevokeApprovalInstruction(multisigAccount: PublicKey,
+                                                transactionAccount: PublicKey,
+                                                owner: PublicKey,
+                                                programId: PublicKey): TransactionInstruction {
+  const revokeApprovalInstruction = new RevokeApprovalInstruction({
+    instructionDiscriminator: MultisigInstruction.RevokeApproval,
+  });
+  return new TransactionInstruction({
+    keys: [
+      {pubkey: multisigAccount, isSigner: false, isWritable: false},
+      {pubkey: transactionAccount, isSigner: false, isWritable: true},
+      {pubkey: owner, isSigner: true, isWritable: false},
+    ],
+    programId: programId,
+    data: revokeApprovalInstruction.toBuffer(),
+  });
+}
+
+class RevokeApprovalInstruction extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(RevokeApprovalSchema, this));
+  }
+}
+
+const RevokeApprovalSchema = { struct: {
+  instructionDiscriminator: "u8",
+}};
@@ -1,23 +1,35 @@
 import {Buffer} from "node:buffer";
 import * as borsh from "borsh";
 import {Assignable} from "../assignable";
-import {PublicKey, TransactionInstruction} from "@solana/web3.js";
+import {AccountMeta, PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
 import {MultisigInstruction} from "./index";
+import {OwnerSchema, Permission, toOwners} from "../state/multisig";
 
 export function createSetOwnersInstruction(
     multisigSigner: PublicKey,
     multisigAccount: PublicKey,
     owners: PublicKey[],
-    programId: PublicKey): TransactionInstruction {
+    programId: PublicKey,
+    permissions: Permission[] = [],
+    weights: number[] = [],
+    payer: PublicKey | null = null): TransactionInstruction {
   const setOwners = new SetOwners({
     instructionDiscriminator: MultisigInstruction.SetOwners,
-    owners: owners.map(owner => owner.toBuffer())
+    owners: toOwners(owners, permissions, weights)
   });
+  const keys: AccountMeta[] = [
+    { pubkey: multisigAccount, isSigner: false, isWritable: true },
+    { pubkey: multisigSigner, isSigner: true, isWritable: false },
+  ];
+  // a payer is only required to grow the owner list beyond its original size
+  if (payer) {
+    keys.push(
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    );
+  }
   return new TransactionInstruction({
-    keys: [
-      { pubkey: multisigAccount, isSigner: false, isWritable: true },
-      { pubkey: multisigSigner, isSigner: true, isWritable: false },
-    ],
+    keys: keys,
     programId: programId,
     data: setOwners.toBuffer(),
   });
@@ -33,6 +45,6 @@ const SetOwnersSchema =
   {
     struct: {
       instructionDiscriminator: "u8",
-      owners: {array: {type: {array: {type: "u8", len: 32}}}}
+      owners: {array: {type: OwnerSchema}}
     }
   };
@@ -0,0 +1,57 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {AccountMeta, PublicKey, SystemProgram, TransactionInstruction} from "@solana/web3.js";
+import {MultisigInstruction} from "./index";
+
+// For SPL mints, `destination` is the recipient token account and `token` names the token program, mint and the token
+// account of the vault the tokens are transferred from.
+export function createUseSpendingLimitInstruction(
+    multisigAccount: PublicKey,
+    spendingLimit: PublicKey,
+    member: PublicKey,
+    vault: PublicKey,
+    destination: PublicKey,
+    amount: bigint | number,
+    programId: PublicKey,
+    token: { programId: PublicKey, mint: PublicKey, source: PublicKey, decimals: number } | null = null): TransactionInstruction {
+  const useSpendingLimit = new UseSpendingLimit({
+    instructionDiscriminator: MultisigInstruction.UseSpendingLimit,
+    amount: amount,
+    decimals: token ? token.decimals : 0
+  });
+  const keys: AccountMeta[] = [
+    { pubkey: multisigAccount, isSigner: false, isWritable: false },
+    { pubkey: spendingLimit, isSigner: false, isWritable: true },
+    { pubkey: member, isSigner: true, isWritable: false },
+    { pubkey: vault, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+    { pubkey: token ? token.programId : SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+  if (token) {
+    keys.push(
+      { pubkey: token.mint, isSigner: false, isWritable: false },
+      { pubkey: token.source, isSigner: false, isWritable: true },
+    );
+  }
+  return new TransactionInstruction({
+    keys: keys,
+    programId: programId,
+    data: useSpendingLimit.toBuffer(),
+  });
+}
+
+export class UseSpendingLimit extends Assignable {
+  toBuffer() {
+    return Buffer.from(borsh.serialize(UseSpendingLimitSchema, this));
+  }
+}
+
+const UseSpendingLimitSchema =
+  {
+    struct: {
+      instructionDiscriminator: "u8",
+      amount: "u64",
+      decimals: "u8"
+    }
+  };
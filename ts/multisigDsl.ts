@@ -1,25 +1,50 @@
 import {
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
   Commitment,
+  Ed25519Program,
   Keypair,
   PublicKey,
   SystemProgram,
   Transaction,
   TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
   VoteProgram
 } from "@solana/web3.js";
-import {BanksTransactionResultWithMeta, ProgramTestContext} from "solana-bankrun";
+import {BanksTransactionResultWithMeta, Clock, ProgramTestContext} from "solana-bankrun";
 import {
+  createAddSpendingLimitInstruction,
   createApproveTransactionInstruction,
+  createApproveWithSignaturesInstruction,
   createCancelTransactionInstruction,
+  createChangeCancelThresholdInstruction,
   createChangeThresholdInstruction,
+  createChangeTimeLockInstruction,
+  createCloseMultisigInstruction,
+  createCloseStaleTransactionInstruction,
+  createCloseTransactionBufferInstruction,
   createCreateMultisigInstruction,
+  createCreateTransactionBufferInstruction,
   createExecuteTransactionInstruction,
+  createExtendTransactionBufferInstruction,
+  createFinalizeTransactionBufferInstruction,
+  createProposeConfigTransactionInstruction,
   createProposeTransactionInstruction,
+  createRejectTransactionInstruction,
+  createRemoveSpendingLimitInstruction,
+  createRevokeApprovalInstruction,
   createSetOwnersInstruction,
-  createSetOwnersAndChangeThresholdInstruction
+  createSetOwnersAndChangeThresholdInstruction,
+  createUseSpendingLimitInstruction,
+  serializeTransactionInstructions,
+  ConfigAction,
+  CreateMultisigOptions,
+  ProposeTransactionOptions,
+  TransactionExpiry
 } from "./instructions";
 import {assert} from "chai";
-import {Transaction as TransactionAccount} from "./state/transaction";
+import {findTransactionAddress, Transaction as TransactionAccount, transactionContentHash} from "./state/transaction";
 import {
   ACCOUNT_SIZE,
   AccountLayout, createInitializeMint2Instruction,
@@ -27,7 +52,17 @@ import {
   MINT_SIZE,
   TOKEN_PROGRAM_ID
 } from "@solana/spl-token";
-import {Multisig} from "./state";
+import {
+  findSpendingLimitAddress,
+  findTransactionBufferAddress,
+  findVaultAddress,
+  Multisig,
+  Permission,
+  SpendingLimit,
+  TransactionBuffer,
+  TransactionReceipt
+} from "./state";
+import {createHash} from "node:crypto";
 
 export interface MultisigAccount {
   address: PublicKey;
@@ -38,12 +73,31 @@ export interface MultisigAccount {
   txMeta: BanksTransactionResultWithMeta;
 }
 
+/** The optional parameters of a multisig created by the DSL. */
+export interface MultisigOptions extends CreateMultisigOptions {
+  initialBalance?: number;
+  useInvalidNonce?: boolean;
+}
+
+/** The optional parameters of a transaction proposed by the DSL. */
+export interface ProposeOptions extends ProposeTransactionOptions {
+  transactionAddress?: PublicKey;
+  proposerIsSigner?: boolean;
+  systemProgramId?: PublicKey;
+  rentPayer?: Keypair;
+}
+
 export interface TokenMint {
   owner: Keypair;
   account: PublicKey;
   decimals: number;
 }
 
+// the vault and ephemeral signers are PDAs signed for by the program, not by the executing transaction
+function isProgramSigner(pubkey: PublicKey, multisigSigner: PublicKey): boolean {
+  return pubkey.equals(multisigSigner) || !PublicKey.isOnCurve(pubkey.toBytes());
+}
+
 export class MultisigDsl {
   readonly programId: PublicKey;
   readonly programTestContext: ProgramTestContext;
@@ -55,9 +109,7 @@ export class MultisigDsl {
 
   async createMultisigWithOwners(threshold: number,
                                  owners: Keypair[],
-                                 initialBalance: number = 0,
-                                 useInvalidNonce: boolean = false,
-                                 systemProgramId: PublicKey = SystemProgram.programId): Promise<MultisigAccount> {
+                                 {initialBalance = 0, useInvalidNonce = false, ...options}: MultisigOptions = {}): Promise<MultisigAccount> {
     const multisig = Keypair.generate();
     const [multisigSigner, nonce] = PublicKey.findProgramAddressSync(
       [multisig.publicKey.toBuffer()],
@@ -65,7 +117,7 @@ export class MultisigDsl {
     );
     const payer = this.programTestContext.payer;
     const createMultisig = createCreateMultisigInstruction(
-      this.programId, threshold, owners, useInvalidNonce ? nonce - 1 : nonce, multisig.publicKey, multisigSigner, payer.publicKey, systemProgramId
+      this.programId, threshold, owners, useInvalidNonce ? nonce - 1 : nonce, multisig.publicKey, multisigSigner, payer.publicKey, options
     );
     const txMeta = await this.createAndProcessTx([createMultisig], payer, [multisig]);
 
@@ -90,15 +142,35 @@ export class MultisigDsl {
 
   async createMultisig(threshold: number, numberOfOwners: number, initialBalance: number = 0): Promise<MultisigAccount> {
     const owners: Keypair[] = Array.from({length: numberOfOwners}, (_, _n) => Keypair.generate());
-    return await this.createMultisigWithOwners(threshold, owners, initialBalance);
+    return await this.createMultisigWithOwners(threshold, owners, {initialBalance});
+  }
+
+  async createMultisigWithTimeLock(threshold: number, numberOfOwners: number, timeLockSeconds: number, initialBalance: number = 0): Promise<MultisigAccount> {
+    const owners: Keypair[] = Array.from({length: numberOfOwners}, (_, _n) => Keypair.generate());
+    return await this.createMultisigWithOwners(threshold, owners, {initialBalance, timeLockSeconds});
+  }
+
+  async createMultisigWithCancelThreshold(threshold: number, numberOfOwners: number, cancelThreshold: number): Promise<MultisigAccount> {
+    const owners: Keypair[] = Array.from({length: numberOfOwners}, (_, _n) => Keypair.generate());
+    return await this.createMultisigWithOwners(threshold, owners, {cancelThreshold});
+  }
+
+  async createMultisigWithPermissions(threshold: number, permissions: Permission[], initialBalance: number = 0): Promise<MultisigAccount> {
+    const owners: Keypair[] = permissions.map(_ => Keypair.generate());
+    return await this.createMultisigWithOwners(threshold, owners, {initialBalance, permissions});
+  }
+
+  async createMultisigWithWeights(threshold: number, weights: number[], initialBalance: number = 0): Promise<MultisigAccount> {
+    const owners: Keypair[] = weights.map(_ => Keypair.generate());
+    return await this.createMultisigWithOwners(threshold, owners, {initialBalance, weights});
   }
 
   async createMultisigWithBadNonce(): Promise<MultisigAccount> {
-    return this.createMultisigWithOwners(2, [Keypair.generate(), Keypair.generate()], 0, true);
+    return this.createMultisigWithOwners(2, [Keypair.generate(), Keypair.generate()], {useInvalidNonce: true});
   }
 
   async createMultisigWithInvalidSystemProgramId(): Promise<MultisigAccount> {
-    return this.createMultisigWithOwners(2, [Keypair.generate(), Keypair.generate()], 0, false, VoteProgram.programId);
+    return this.createMultisigWithOwners(2, [Keypair.generate(), Keypair.generate()], {systemProgramId: VoteProgram.programId});
   }
 
     async createMultisigWithIncorrectSystemProgram(threshold: number, owners: Keypair[]): Promise<MultisigAccount> {
@@ -109,7 +181,7 @@ export class MultisigDsl {
     );
     const payer = this.programTestContext.payer;
     const createMultisig = createCreateMultisigInstruction(
-        this.programId, threshold, owners, nonce, multisig.publicKey, multisigSigner, payer.publicKey, VoteProgram.programId
+        this.programId, threshold, owners, nonce, multisig.publicKey, multisigSigner, payer.publicKey, {systemProgramId: VoteProgram.programId}
     );
     const txMeta = await this.createAndProcessTx([createMultisig], payer, [multisig]);
 
@@ -126,47 +198,164 @@ export class MultisigDsl {
   async proposeTransaction(proposer: Keypair,
                            instructions: TransactionInstruction[],
                            multisig: PublicKey,
-                           transactionAddress?: Keypair,
-                           proposerIsSigner = true,
-                           systemProgramId: PublicKey = SystemProgram.programId): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
-    const transactionAccount = transactionAddress ? transactionAddress : Keypair.generate();
+                           {
+                             transactionAddress,
+                             proposerIsSigner = true,
+                             systemProgramId = SystemProgram.programId,
+                             rentPayer = this.programTestContext.payer,
+                             ...options
+                           }: ProposeOptions = {}): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
+    const transactionAccount = transactionAddress ? transactionAddress : await this.nextTransactionAddress(multisig);
     const proposeTx = createProposeTransactionInstruction(multisig,
-        transactionAccount.publicKey,
+        transactionAccount,
         proposer.publicKey,
-        this.programTestContext.payer.publicKey,
+        rentPayer.publicKey,
         this.programId,
         instructions,
         proposerIsSigner,
-        systemProgramId);
-    const additionalSigners = proposerIsSigner ? [proposer, transactionAccount] : [transactionAccount];
+        systemProgramId,
+        options);
+    const additionalSigners = proposerIsSigner ? [proposer] : [];
+    if (rentPayer !== this.programTestContext.payer) {
+      additionalSigners.push(rentPayer);
+    }
     let txMeta = await this.createAndProcessTx([proposeTx], this.programTestContext.payer, additionalSigners);
-    return [transactionAccount.publicKey, txMeta];
+    return [transactionAccount, txMeta];
+  }
+
+  async proposeConfigTransaction(proposer: Keypair,
+                                 actions: ConfigAction[],
+                                 multisig: PublicKey,
+                                 {expiry = null, memo = null, descriptionHash = null}: ProposeTransactionOptions = {}): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
+    const transactionAccount = await this.nextTransactionAddress(multisig);
+    const proposeTx = createProposeConfigTransactionInstruction(multisig,
+        transactionAccount,
+        proposer.publicKey,
+        this.programTestContext.payer.publicKey,
+        this.programId,
+        actions,
+        SystemProgram.programId,
+        expiry,
+        memo,
+        descriptionHash);
+    const txMeta = await this.createAndProcessTx([proposeTx], this.programTestContext.payer, [proposer]);
+    return [transactionAccount, txMeta];
+  }
+
+  // uploads the serialized instructions in chunks (one transaction per chunk), returning the buffer address and the
+  // result of the last chunk
+  async uploadTransactionBuffer(creator: Keypair,
+                                instructions: TransactionInstruction[],
+                                multisig: PublicKey,
+                                chunkSize: number = 800,
+                                bufferIndex: number = 0,
+                                finalHash?: Uint8Array,
+                                {memo = null, descriptionHash = null}: ProposeTransactionOptions = {}): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
+    const buffer = serializeTransactionInstructions(instructions);
+    const hash = finalHash ? finalHash : createHash("sha256").update(buffer).digest();
+    const transactionBuffer = findTransactionBufferAddress(multisig, creator.publicKey, bufferIndex, this.programId);
+    const create = createCreateTransactionBufferInstruction(multisig, creator.publicKey, this.programTestContext.payer.publicKey,
+        bufferIndex, 0, 0, null, [], hash, buffer.length, buffer.subarray(0, chunkSize), this.programId, memo, descriptionHash);
+    let txMeta = await this.createAndProcessTx([create], this.programTestContext.payer, [creator]);
+    for (let offset = chunkSize; offset < buffer.length && txMeta.result === null; offset += chunkSize) {
+      const extend = createExtendTransactionBufferInstruction(transactionBuffer, creator.publicKey,
+          this.programTestContext.payer.publicKey, buffer.subarray(offset, offset + chunkSize), this.programId);
+      txMeta = await this.createAndProcessTx([extend], this.programTestContext.payer, [creator]);
+    }
+    return [transactionBuffer, txMeta];
+  }
+
+  async finalizeTransactionBuffer(creator: Keypair, multisig: PublicKey, bufferIndex: number = 0): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
+    const transactionAccount = await this.nextTransactionAddress(multisig);
+    const transactionBuffer = findTransactionBufferAddress(multisig, creator.publicKey, bufferIndex, this.programId);
+    const finalize = createFinalizeTransactionBufferInstruction(multisig, transactionAccount, transactionBuffer,
+        creator.publicKey, this.programTestContext.payer.publicKey, this.programId);
+    const txMeta = await this.createAndProcessTx([finalize], this.programTestContext.payer, [creator]);
+    return [transactionAccount, txMeta];
+  }
+
+  async closeTransactionBuffer(creator: Keypair, multisig: PublicKey, bufferIndex: number = 0): Promise<BanksTransactionResultWithMeta> {
+    const transactionBuffer = findTransactionBufferAddress(multisig, creator.publicKey, bufferIndex, this.programId);
+    const close = createCloseTransactionBufferInstruction(transactionBuffer, creator.publicKey, this.programId);
+    return this.createAndProcessTx([close], this.programTestContext.payer, [creator]);
+  }
+
+  async getTransactionReceipt(address: PublicKey): Promise<TransactionReceipt> {
+    const transactionReceiptAccountInfo = await this.programTestContext.banksClient.getAccount(address);
+    assert.isNotNull(transactionReceiptAccountInfo);
+    return TransactionReceipt.deserialize(transactionReceiptAccountInfo?.data);
+  }
+
+  async getTransactionBuffer(address: PublicKey): Promise<TransactionBuffer> {
+    const transactionBufferAccountInfo = await this.programTestContext.banksClient.getAccount(address);
+    assert.isNotNull(transactionBufferAccountInfo);
+    return TransactionBuffer.deserialize(transactionBufferAccountInfo?.data);
+  }
+
+  async nextTransactionAddress(multisig: PublicKey): Promise<PublicKey> {
+    const multisigAccount = await this.getMultisig(multisig);
+    return findTransactionAddress(multisig, multisigAccount["transaction_index"] + 1n, this.programId);
+  }
+
+  async proposeTransactionWithExpiry(proposer: Keypair,
+                                     instructions: TransactionInstruction[],
+                                     multisig: PublicKey,
+                                     expiry: TransactionExpiry): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
+    return this.proposeTransaction(proposer, instructions, multisig, {expiry});
+  }
+
+  async proposeTransactionForVault(proposer: Keypair,
+                                   instructions: TransactionInstruction[],
+                                   multisig: PublicKey,
+                                   vaultIndex: number): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
+    return this.proposeTransaction(proposer, instructions, multisig, {vaultIndex});
   }
 
   async proposeTransactionWithIncorrectSystemProgram(proposer: Keypair,
                                                      instructions: TransactionInstruction[],
                                                      multisig: PublicKey): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
-    return this.proposeTransaction(proposer, instructions, multisig, Keypair.generate(), true, VoteProgram.programId);
+    return this.proposeTransaction(proposer, instructions, multisig, {systemProgramId: VoteProgram.programId});
   }
 
   async proposeTransactionWithProposerNotSigner(proposer: Keypair,
                                                 instructions: TransactionInstruction[],
                                                 multisig: PublicKey): Promise<[PublicKey, BanksTransactionResultWithMeta]> {
-    return this.proposeTransaction(proposer, instructions, multisig, Keypair.generate(), false, SystemProgram.programId);
+    return this.proposeTransaction(proposer, instructions, multisig, {proposerIsSigner: false});
   }
 
   async approveTransaction(approver: Keypair,
                            multisig: PublicKey,
                            transactionAddress: PublicKey,
                            approverIsSigner: boolean = true,
-                           transactionIsWritable: boolean = true): Promise<BanksTransactionResultWithMeta> {
-    let approve = createApproveTransactionInstruction(multisig, transactionAddress, approver.publicKey, this.programId, approverIsSigner, transactionIsWritable);
+                           transactionIsWritable: boolean = true,
+                           expectedHash: Uint8Array | null = null): Promise<BanksTransactionResultWithMeta> {
+    let approve = createApproveTransactionInstruction(multisig, transactionAddress, approver.publicKey, this.programId,
+        approverIsSigner, transactionIsWritable, expectedHash);
     const additionalSigners = approverIsSigner ? [approver] : [];
     return this.createAndProcessTx([approve], this.programTestContext.payer, additionalSigners);
   }
 
-  createSetOwnersInstruction(multisig: MultisigAccount, newOwners: PublicKey[]): TransactionInstruction {
-    return createSetOwnersInstruction(multisig.signer, multisig.address, newOwners, this.programId);
+  // Relays the approvals of the given owners, signed off-chain over the content hash of the transaction.
+  async approveWithSignatures(approvers: Keypair[], multisig: PublicKey, transactionAddress: PublicKey): Promise<BanksTransactionResultWithMeta> {
+    const contentHash = transactionContentHash(transactionAddress, await this.getTransactionAccount(transactionAddress));
+    const signatures = approvers.map(approver =>
+        Ed25519Program.createInstructionWithPrivateKey({privateKey: approver.secretKey, message: contentHash}));
+    const approve = createApproveWithSignaturesInstruction(multisig, transactionAddress, this.programId);
+    return this.createAndProcessTx([...signatures, approve], this.programTestContext.payer);
+  }
+
+  async revokeApproval(owner: Keypair, multisig: PublicKey, transactionAddress: PublicKey): Promise<BanksTransactionResultWithMeta> {
+    const revoke = createRevokeApprovalInstruction(multisig, transactionAddress, owner.publicKey, this.programId);
+    return this.createAndProcessTx([revoke], this.programTestContext.payer, [owner]);
+  }
+
+  async rejectTransaction(rejector: Keypair, multisig: PublicKey, transactionAddress: PublicKey): Promise<BanksTransactionResultWithMeta> {
+    const reject = createRejectTransactionInstruction(multisig, transactionAddress, rejector.publicKey, this.programId);
+    return this.createAndProcessTx([reject], this.programTestContext.payer, [rejector]);
+  }
+
+  createSetOwnersInstruction(multisig: MultisigAccount, newOwners: PublicKey[], permissions: Permission[] = [], weights: number[] = [], payer: PublicKey | null = null): TransactionInstruction {
+    return createSetOwnersInstruction(multisig.signer, multisig.address, newOwners, this.programId, permissions, weights, payer);
   }
 
   createSetOwnersInstructionManualSigner(multisigSigner: PublicKey, multisigAddress: PublicKey, newOwners: PublicKey[]): TransactionInstruction {
@@ -181,14 +370,65 @@ export class MultisigDsl {
     return createChangeThresholdInstruction(multisigSigner, multisigAddress, newThreshold, this.programId);
   }
 
-  createSetOwnersAndChangeThresholdInstruction(multisig: MultisigAccount, newOwners: PublicKey[], newThreshold: number): TransactionInstruction {
-    return createSetOwnersAndChangeThresholdInstruction(multisig.signer, multisig.address, newOwners, newThreshold, this.programId);
+  createSetOwnersAndChangeThresholdInstruction(multisig: MultisigAccount, newOwners: PublicKey[], newThreshold: number, payer: PublicKey | null = null): TransactionInstruction {
+    return createSetOwnersAndChangeThresholdInstruction(multisig.signer, multisig.address, newOwners, newThreshold, this.programId, [], [], payer);
   }
 
   createSetOwnersAndChangeThresholdInstructionManualSigner(multisigSigner: PublicKey, multisigAddress: PublicKey, newOwners: PublicKey[], newThreshold: number): TransactionInstruction {
     return createSetOwnersAndChangeThresholdInstruction(multisigSigner, multisigAddress, newOwners, newThreshold, this.programId);
   }
 
+  createChangeCancelThresholdInstruction(multisig: MultisigAccount, newCancelThreshold: number): TransactionInstruction {
+    return createChangeCancelThresholdInstruction(multisig.signer, multisig.address, newCancelThreshold, this.programId);
+  }
+
+  createChangeTimeLockInstruction(multisig: MultisigAccount, newTimeLockSeconds: number): TransactionInstruction {
+    return createChangeTimeLockInstruction(multisig.signer, multisig.address, newTimeLockSeconds, this.programId);
+  }
+
+  // the multisig signer pays for the spending limit account, so it needs to be funded
+  createAddSpendingLimitInstruction(multisig: MultisigAccount, vaultIndex: number, mint: PublicKey, amount: number, periodSeconds: number, members: PublicKey[]): TransactionInstruction {
+    return createAddSpendingLimitInstruction(multisig.signer, multisig.address, multisig.signer, vaultIndex, mint, amount, periodSeconds, members, this.programId);
+  }
+
+  createRemoveSpendingLimitInstruction(multisig: MultisigAccount, spendingLimit: PublicKey, refundee: PublicKey): TransactionInstruction {
+    return createRemoveSpendingLimitInstruction(multisig.signer, multisig.address, spendingLimit, refundee, this.programId);
+  }
+
+  createCloseMultisigInstruction(multisig: MultisigAccount, recipient: PublicKey): TransactionInstruction {
+    return createCloseMultisigInstruction(multisig.signer, multisig.address, recipient, this.programId);
+  }
+
+  async useSpendingLimit(member: Keypair, multisig: MultisigAccount, vaultIndex: number, destination: PublicKey, amount: number): Promise<BanksTransactionResultWithMeta> {
+    const spendingLimit = findSpendingLimitAddress(multisig.address, vaultIndex, PublicKey.default, this.programId);
+    const vault = findVaultAddress(multisig.address, vaultIndex, this.programId);
+    const use = createUseSpendingLimitInstruction(multisig.address, spendingLimit, member.publicKey, vault, destination, amount, this.programId);
+    return this.createAndProcessTx([use], this.programTestContext.payer, [member]);
+  }
+
+  async useTokenSpendingLimit(member: Keypair, multisig: MultisigAccount, vaultIndex: number, mint: TokenMint, source: PublicKey, destination: PublicKey, amount: number): Promise<BanksTransactionResultWithMeta> {
+    const spendingLimit = findSpendingLimitAddress(multisig.address, vaultIndex, mint.account, this.programId);
+    const vault = findVaultAddress(multisig.address, vaultIndex, this.programId);
+    const use = createUseSpendingLimitInstruction(multisig.address, spendingLimit, member.publicKey, vault, destination, amount, this.programId,
+        {programId: TOKEN_PROGRAM_ID, mint: mint.account, source: source, decimals: mint.decimals});
+    return this.createAndProcessTx([use], this.programTestContext.payer, [member]);
+  }
+
+  async getSpendingLimit(address: PublicKey): Promise<SpendingLimit> {
+    const spendingLimitAccountInfo = await this.programTestContext.banksClient.getAccount(address);
+    assert.isNotNull(spendingLimitAccountInfo);
+    return SpendingLimit.deserialize(spendingLimitAccountInfo?.data);
+  }
+
+  async fund(address: PublicKey, lamports: number) {
+    const transfer = SystemProgram.transfer({
+      fromPubkey: this.programTestContext.payer.publicKey,
+      lamports: lamports,
+      toPubkey: address,
+    });
+    await this.createAndProcessTx([transfer], this.programTestContext.payer);
+  }
+
   async assertBalance(address: PublicKey, expectedBalance: number) {
     let actualBalance = await this.programTestContext.banksClient.getBalance(address, "confirmed");
     assert.strictEqual(actualBalance, BigInt(expectedBalance));
@@ -206,10 +446,12 @@ export class MultisigDsl {
       multisigSigner: PublicKey,
       multisigAddress: PublicKey,
       executor: Keypair,
-      refundee: PublicKey) {
+      refundee: PublicKey,
+      addressLookupTables: AddressLookupTableAccount[] = [],
+      maxInstructions: number | null = null) {
     const accounts = ixs.flatMap(ix =>
         ix.keys
-            .map((meta) => meta.pubkey.equals(multisigSigner)? {...meta, isSigner: false} : meta)
+            .map((meta) => isProgramSigner(meta.pubkey, multisigSigner) ? {...meta, isSigner: false} : meta)
             .concat({
               pubkey: ix.programId,
               isWritable: false,
@@ -222,8 +464,13 @@ export class MultisigDsl {
         return JSON.stringify(obj) === _value;
       });
     });
+    const tableAccounts = addressLookupTables.map(table => ({ pubkey: table.key, isWritable: false, isSigner: false }));
     const execute = createExecuteTransactionInstruction(
-        multisigAddress, multisigSigner, txAccount, refundee, executor.publicKey, dedupedAccounts, this.programId);
+        multisigAddress, multisigSigner, txAccount, refundee, executor.publicKey, dedupedAccounts.concat(tableAccounts), this.programId,
+        maxInstructions);
+    if (addressLookupTables.length > 0) {
+      return await this.createAndProcessV0Tx([execute], this.programTestContext.payer, [executor], addressLookupTables);
+    }
     return await this.createAndProcessTx([execute], this.programTestContext.payer, [executor]);
   }
 
@@ -243,6 +490,11 @@ export class MultisigDsl {
     return await this.createAndProcessTx([cancel], this.programTestContext.payer, [executor]);
   }
 
+  async closeStaleTransaction(txAddress: PublicKey, multisigAddress: PublicKey, rentPayer: PublicKey) {
+    const closeStale = createCloseStaleTransactionInstruction(multisigAddress, txAddress, rentPayer, this.programId);
+    return await this.createAndProcessTx([closeStale], this.programTestContext.payer);
+  }
+
   async createTokenMint(decimals: number = 3, initialSolBalance: number = 7_000_000): Promise<TokenMint> {
     const mintOwner = Keypair.generate();
 
@@ -337,6 +589,60 @@ export class MultisigDsl {
     return await this.programTestContext.banksClient.tryProcessTransaction(tx);
   }
 
+  // loads the accounts found in the given address lookup tables via the tables instead of listing them in the message
+  async createAndProcessV0Tx(instructions: TransactionInstruction[], payer: Keypair, additionalSigners: Keypair[], addressLookupTables: AddressLookupTableAccount[]): Promise<BanksTransactionResultWithMeta> {
+    const [latestBlockhash, _blockHeight] = await this.programTestContext.banksClient.getLatestBlockhash();
+    const message = new TransactionMessage({
+      payerKey: payer.publicKey,
+      recentBlockhash: latestBlockhash,
+      instructions: instructions,
+    }).compileToV0Message(addressLookupTables);
+    const tx = new VersionedTransaction(message);
+    tx.sign([payer, ...additionalSigners]);
+    return await this.programTestContext.banksClient.tryProcessTransaction(tx);
+  }
+
+  // writes an active address lookup table directly (creating one via the program would require a recent slot hash)
+  createAddressLookupTable(addresses: PublicKey[], owner: PublicKey = AddressLookupTableProgram.programId): AddressLookupTableAccount {
+    const address = Keypair.generate().publicKey;
+    const meta = Buffer.alloc(56);
+    meta.writeUInt32LE(1, 0);                           // type: lookup table
+    meta.writeBigUInt64LE(BigInt("0xffffffffffffffff"), 4);  // deactivation slot: not deactivated
+    meta.writeBigUInt64LE(0n, 12);                      // last extended slot
+    meta.writeUInt8(0, 20);                             // last extended slot start index
+    meta.writeUInt8(0, 21);                             // authority: none (frozen)
+    const data = Buffer.concat([meta, ...addresses.map(address => address.toBuffer())]);
+    this.programTestContext.setAccount(address, {
+      lamports: 1_000_000_000,
+      data: data,
+      owner: owner,
+      executable: false,
+    });
+    return new AddressLookupTableAccount({ key: address, state: AddressLookupTableAccount.deserialize(data) });
+  }
+
+  // appends the given addresses to the lookup table, as its authority could (before it is frozen)
+  async extendAddressLookupTable(table: AddressLookupTableAccount, addresses: PublicKey[]): Promise<AddressLookupTableAccount> {
+    const account = await this.programTestContext.banksClient.getAccount(table.key);
+    const data = Buffer.concat([Buffer.from(account.data), ...addresses.map(address => address.toBuffer())]);
+    this.programTestContext.setAccount(table.key, {...account, data: data});
+    return new AddressLookupTableAccount({ key: table.key, state: AddressLookupTableAccount.deserialize(data) });
+  }
+
+  async advanceClock(seconds: number) {
+    // also move to the next slot so that re-submitting an identical transaction is not rejected as already processed
+    const currentSlot = await this.programTestContext.banksClient.getSlot();
+    this.programTestContext.warpToSlot(currentSlot + 1n);
+    const clock = await this.programTestContext.banksClient.getClock();
+    this.programTestContext.setClock(new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds),
+    ));
+  }
+
   async getMultisig(multisigAddress: PublicKey): Promise<Multisig>
   {
     const multisigAccountInfo = await this.programTestContext.banksClient.getAccount(multisigAddress);
@@ -370,16 +676,16 @@ export class MultisigDsl {
       executor: Keypair,
       refundee: PublicKey
   ) {
-    const txAccount = Keypair.generate();
-    const propose = createProposeTransactionInstruction(multisigAddress, txAccount.publicKey, proposer.publicKey,
+    const txAccount = await this.nextTransactionAddress(multisigAddress);
+    const propose = createProposeTransactionInstruction(multisigAddress, txAccount, proposer.publicKey,
         this.programTestContext.payer.publicKey, this.programId, instructions, true, SystemProgram.programId);
 
     const approvals = await Promise.all(signers.map(async signer =>
-        createApproveTransactionInstruction(multisigAddress, txAccount.publicKey, signer.publicKey, this.programId)
+        createApproveTransactionInstruction(multisigAddress, txAccount, signer.publicKey, this.programId)
     ));
 
     const accounts = instructions.flatMap(ix =>
-        ix.keys.map((meta) => meta.pubkey.equals(multisigSigner) ? {...meta, isSigner: false} : meta)
+        ix.keys.map((meta) => isProgramSigner(meta.pubkey, multisigSigner) ? {...meta, isSigner: false} : meta)
             .concat({
               pubkey: ix.programId,
               isWritable: false,
@@ -393,8 +699,8 @@ export class MultisigDsl {
       });
     });
     const execute = createExecuteTransactionInstruction(multisigAddress, multisigSigner,
-        txAccount.publicKey, refundee, executor.publicKey, dedupedAccounts, this.programId);
+        txAccount, refundee, executor.publicKey, dedupedAccounts, this.programId);
 
-    return await this.createAndProcessTx([propose, ...approvals, execute], this.programTestContext.payer, [txAccount, proposer, ...signers]);
+    return await this.createAndProcessTx([propose, ...approvals, execute], this.programTestContext.payer, [proposer, ...signers]);
   }
 }
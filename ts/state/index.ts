@@ -1 +1,4 @@
 export * from "./multisig";
+export * from "./spendingLimit";
+export * from "./transactionBuffer";
+export * from "./transactionReceipt";
@@ -1,6 +1,14 @@
 import {Buffer} from "node:buffer";
 import * as borsh from "borsh";
 import {Assignable} from "../assignable";
+import {PublicKey} from "@solana/web3.js";
+
+export enum Permission {
+  Propose = 1 << 0,
+  Vote = 1 << 1,
+  Execute = 1 << 2,
+  All = Propose | Vote | Execute,
+}
 
 export class Multisig extends Assignable {
   static deserialize(data: Uint8Array): Multisig {
@@ -8,13 +16,52 @@ export class Multisig extends Assignable {
   }
 }
 
+export const OwnerSchema =
+  {
+    struct: {
+      pubkey: {array: {type: "u8", len: 32}},
+      permissions: "u8",
+      weight: "u16",
+    }
+  };
+
+// Pairs the given owner keys with their permissions and weights, granting all permissions and a weight of 1 to owners
+// without an explicit entry.
+export function toOwners(owners: PublicKey[], permissions: Permission[] = [], weights: number[] = []) {
+  return owners.map((owner, index) => ({
+    pubkey: owner.toBuffer(),
+    permissions: permissions[index] ?? Permission.All,
+    weight: weights[index] ?? 1,
+  }));
+}
+
+// Derives the signer PDA of the given vault of a multisig (vault 0 is the multisig signer derived from the nonce).
+export function findVaultAddress(multisig: PublicKey, vaultIndex: number, programId: PublicKey): PublicKey {
+  if (vaultIndex === 0) {
+    const [signer, _nonce] = PublicKey.findProgramAddressSync([multisig.toBuffer()], programId);
+    return signer;
+  }
+  const [vault, _bump] = PublicKey.findProgramAddressSync(
+    [multisig.toBuffer(), Buffer.from("vault"), Buffer.from([vaultIndex])],
+    programId
+  );
+  return vault;
+}
+
 export const MultisigSchema =
   {
     struct: {
-      owners: {array: {type: {array: {type: "u8", len: 32}}}},
-      threshold: "u8",
+      discriminator: "u8",
+      version: "u8",
+      owners: {array: {type: OwnerSchema}},
+      threshold: "u16",
+      cancel_threshold: "u8",
       nonce: "u8",
       owner_set_seqno: "u32",
+      time_lock_seconds: "u32",
+      transaction_index: "u64",
+      pending_transactions: "u32",
+      spending_limits: "u32",
       padding: {array: {type: "u8"}},
     }
   };
@@ -0,0 +1,35 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {PublicKey} from "@solana/web3.js";
+
+export class SpendingLimit extends Assignable {
+  static deserialize(data: Uint8Array): SpendingLimit {
+    return borsh.deserialize(SpendingLimitSchema, Buffer.from(data));
+  }
+}
+
+// Derives the address of the spending limit for the given vault and mint (`PublicKey.default` for SOL) of a multisig.
+export function findSpendingLimitAddress(multisig: PublicKey, vaultIndex: number, mint: PublicKey, programId: PublicKey): PublicKey {
+  const [address, _bump] = PublicKey.findProgramAddressSync(
+    [multisig.toBuffer(), Buffer.from("spending_limit"), Buffer.from([vaultIndex]), mint.toBuffer()],
+    programId
+  );
+  return address;
+}
+
+export const SpendingLimitSchema =
+  {
+    struct: {
+      discriminator: "u8",
+      version: "u8",
+      multisig: {array: {type: "u8", len: 32}},
+      vault_index: "u8",
+      mint: {array: {type: "u8", len: 32}},
+      amount: "u64",
+      period_seconds: "i64",
+      members: {array: {type: {array: {type: "u8", len: 32}}}},
+      remaining_amount: "u64",
+      period_started_at: "i64",
+    }
+  };
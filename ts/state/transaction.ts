@@ -1,6 +1,11 @@
 import {Buffer} from "node:buffer";
+import {createHash} from "node:crypto";
 import * as borsh from "borsh";
 import {Assignable} from "../assignable";
+import {TransactionExpirySchema} from "../instructions/proposeTransaction";
+import {ConfigActionSchema} from "../instructions/proposeConfigTransaction";
+import {ExecutionReceiptSchema} from "./transactionReceipt";
+import {PublicKey} from "@solana/web3.js";
 
 export class Transaction extends Assignable {
   static deserialize(data: Uint8Array): Transaction {
@@ -8,8 +13,44 @@ export class Transaction extends Assignable {
   }
 }
 
+// Derives the address of the transaction account with the given (1-based) index from its multisig.
+export function findTransactionAddress(multisig: PublicKey, transactionIndex: bigint, programId: PublicKey): PublicKey {
+  const index = Buffer.alloc(8);
+  index.writeBigUInt64LE(transactionIndex);
+  const [address, _bump] = PublicKey.findProgramAddressSync(
+    [multisig.toBuffer(), Buffer.from("transaction"), index],
+    programId
+  );
+  return address;
+}
+
+// Derives the address of an ephemeral signer of a transaction, which the multisig signs for on execution (e.g. to
+// create a new account at that address).
+export function findEphemeralSignerAddress(transaction: PublicKey, index: number, programId: PublicKey): PublicKey {
+  const [address, _bump] = PublicKey.findProgramAddressSync(
+    [transaction.toBuffer(), Buffer.from("ephemeral_signer"), Buffer.from([index])],
+    programId
+  );
+  return address;
+}
+
+// The canonical hash of the contents of a transaction (everything but its votes, proposer, rent payer and creation
+// time), which owners can sign off-chain to approve it via `approve_with_signatures` (mirrors
+// `Transaction::content_hash` of the program).
+export function transactionContentHash(transactionAddress: PublicKey, transaction: Transaction): Buffer {
+  const contents = borsh.serialize(TransactionContentSchema, transaction);
+  return createHash("sha256")
+      .update(Buffer.from("multisig_native_transaction"))
+      .update(transactionAddress.toBuffer())
+      .update(contents)
+      .digest();
+}
+
 export const TransactionSchema = { struct: {
+  discriminator: "u8",
+  version: "u8",
   multisig: { array: { type: "u8", len: 32 }},
+  transaction_index: "u64",
   instructions: { array: { type: { struct: {
     program_id: { array: { type: "u8", len: 32 }},
     accounts: { array: { type: { struct: {
@@ -17,8 +58,47 @@ export const TransactionSchema = { struct: {
       is_signer: "bool",
       is_writable: "bool"
     }}}},
+    lookup_accounts: { array: { type: { struct: {
+      position: "u8",
+      table_index: "u8",
+      address_index: "u8",
+      is_writable: "bool"
+    }}}},
     data: { array: { type: "u8" }}
   }}}},
+  config_actions: { array: { type: ConfigActionSchema }},
+  address_lookup_tables: { array: { type: { array: { type: "u8", len: 32 }}}},
+  vault_index: "u8",
+  ephemeral_signers: "u8",
+  executed_up_to: "u16",
+  receipts: { array: { type: ExecutionReceiptSchema }},
   signers: { array: { type: "bool" }},
-  owner_set_seqno: "u32"
+  rejectors: { array: { type: "bool" }},
+  revokers: { array: { type: "bool" }},
+  rejected: "bool",
+  cancellers: { array: { type: "bool" }},
+  cancellers_owner_set_seqno: "u32",
+  owner_set_seqno: "u32",
+  threshold_reached_at: "i64",
+  proposer: { array: { type: "u8", len: 32 }},
+  rent_payer: { array: { type: "u8", len: 32 }},
+  created_at_slot: "u64",
+  created_at: "i64",
+  memo: { option: "string" },
+  description_hash: { option: { array: { type: "u8", len: 32 }}},
+  expiry: { option: TransactionExpirySchema }
+}};
+
+const TransactionContentSchema = { struct: {
+  multisig: TransactionSchema.struct.multisig,
+  transaction_index: TransactionSchema.struct.transaction_index,
+  instructions: TransactionSchema.struct.instructions,
+  config_actions: TransactionSchema.struct.config_actions,
+  address_lookup_tables: TransactionSchema.struct.address_lookup_tables,
+  vault_index: TransactionSchema.struct.vault_index,
+  ephemeral_signers: TransactionSchema.struct.ephemeral_signers,
+  owner_set_seqno: TransactionSchema.struct.owner_set_seqno,
+  expiry: TransactionSchema.struct.expiry,
+  memo: TransactionSchema.struct.memo,
+  description_hash: TransactionSchema.struct.description_hash
 }};
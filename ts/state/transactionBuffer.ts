@@ -0,0 +1,40 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+import {TransactionExpirySchema} from "../instructions/proposeTransaction";
+import {PublicKey} from "@solana/web3.js";
+
+export class TransactionBuffer extends Assignable {
+  static deserialize(data: Uint8Array): TransactionBuffer {
+    return borsh.deserialize(TransactionBufferSchema, Buffer.from(data));
+  }
+}
+
+// Derives the address of the transaction buffer with the given index of a creator.
+export function findTransactionBufferAddress(multisig: PublicKey, creator: PublicKey, bufferIndex: number, programId: PublicKey): PublicKey {
+  const [address, _bump] = PublicKey.findProgramAddressSync(
+    [multisig.toBuffer(), Buffer.from("transaction_buffer"), creator.toBuffer(), Buffer.from([bufferIndex])],
+    programId
+  );
+  return address;
+}
+
+export const TransactionBufferSchema =
+  {
+    struct: {
+      discriminator: "u8",
+      version: "u8",
+      multisig: {array: {type: "u8", len: 32}},
+      creator: {array: {type: "u8", len: 32}},
+      buffer_index: "u8",
+      vault_index: "u8",
+      ephemeral_signers: "u8",
+      expiry: {option: TransactionExpirySchema},
+      address_lookup_tables: {array: {type: {array: {type: "u8", len: 32}}}},
+      memo: {option: "string"},
+      description_hash: {option: {array: {type: "u8", len: 32}}},
+      final_hash: {array: {type: "u8", len: 32}},
+      final_size: "u32",
+      buffer: {array: {type: "u8"}},
+    }
+  };
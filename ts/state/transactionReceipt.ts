@@ -0,0 +1,26 @@
+import {Buffer} from "node:buffer";
+import * as borsh from "borsh";
+import {Assignable} from "../assignable";
+
+// Replaces the transaction account once all of its instructions have been executed.
+export class TransactionReceipt extends Assignable {
+  static deserialize(data: Uint8Array): TransactionReceipt {
+    return borsh.deserialize(TransactionReceiptSchema, Buffer.from(data));
+  }
+}
+
+export const ExecutionReceiptSchema = { struct: {
+  instruction_index: "u16",
+  return_data_hash: { array: { type: "u8", len: 32 }},
+  slot: "u64"
+}};
+
+export const TransactionReceiptSchema = { struct: {
+  discriminator: "u8",
+  version: "u8",
+  multisig: { array: { type: "u8", len: 32 }},
+  transaction_index: "u64",
+  content_hash: { array: { type: "u8", len: 32 }},
+  executor: { array: { type: "u8", len: 32 }},
+  receipts: { array: { type: ExecutionReceiptSchema }}
+}};